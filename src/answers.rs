@@ -0,0 +1,223 @@
+//! Expected answers, kept in a file next to each input.
+//!
+//! The answers to the input `2021/day_8.txt` live in `2021/day_8.answers`:
+//!
+//! ```text
+//! part 1: 284
+//! part 2: 973499
+//! ```
+//!
+//! Answers that span several lines (rendered letters) start on the line after
+//! their `part N:` header and run until the next header.
+
+use super::error::{source_lines, ParseError};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Extension of the answers file that sits next to an input.
+pub const EXTENSION: &str = "answers";
+
+/// Path of the answers file for the input at `input`.
+pub fn path_for(input: &Path) -> PathBuf {
+    input.with_extension(EXTENSION)
+}
+
+/// Trailing whitespace on a line of a rendered answer is not significant.
+fn normalize(answer: &str) -> String {
+    let lines = answer.lines().map(str::trim_end).collect::<Vec<_>>();
+    lines.join("\n").trim_end_matches('\n').to_string()
+}
+
+/// The recorded answers to both parts of one input, either of which may be
+/// unknown.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    part_1: Option<String>,
+    part_2: Option<String>,
+}
+
+/// Outcome of comparing an answer against the recorded one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Answers {
+    fn slot(&mut self, part: u32) -> Option<&mut Option<String>> {
+        match part {
+            1 => Some(&mut self.part_1),
+            2 => Some(&mut self.part_2),
+            _ => None,
+        }
+    }
+
+    pub fn get(&self, part: u32) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Records `answer` for `part`, returning false if there is no such part.
+    pub fn set(&mut self, part: u32, answer: &str) -> bool {
+        match self.slot(part) {
+            Some(slot) => {
+                *slot = Some(normalize(answer));
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn check(&self, part: u32, answer: &str) -> Verdict {
+        match self.get(part) {
+            Some(expected) if normalize(answer) == expected => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+        let mut block: Option<(u32, Vec<&str>)> = None;
+
+        for line in source_lines(s) {
+            let header = line.text.strip_prefix("part ").map(|rest| {
+                let (part, answer) = rest.split_once(':').unwrap_or((rest, ""));
+                (part, answer.trim())
+            });
+            let (part, answer) = match header {
+                Some(header) => header,
+                None => match &mut block {
+                    Some((_, lines)) => {
+                        lines.push(line.text);
+                        continue;
+                    }
+                    None if line.text.trim().is_empty() => continue,
+                    None => return Err(line.error(line.text, "'part <N>: <answer>'")),
+                },
+            };
+
+            if let Some((part, lines)) = block.take() {
+                answers.set(part, &lines.join("\n"));
+            }
+            let number = line.parse::<u32>(part, "part")?;
+            if answers.slot(number).is_none() {
+                return Err(line.error(part, "part 1 or 2"));
+            }
+            if answer.is_empty() {
+                block = Some((number, Vec::new()));
+            } else {
+                answers.set(number, answer);
+            }
+        }
+        if let Some((part, lines)) = block {
+            answers.set(part, &lines.join("\n"));
+        }
+
+        Ok(answers)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for part in 1..=2 {
+            match self.get(part) {
+                Some(answer) if answer.contains('\n') => {
+                    writeln!(f, "part {}:", part)?;
+                    writeln!(f, "{}", answer)?;
+                }
+                Some(answer) => writeln!(f, "part {}: {}", part, answer)?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { .. } => write!(f, "fail"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Checks an answer computed in a test against the answers recorded for its
+/// input, failing if they differ or nothing was recorded.
+#[cfg(test)]
+pub fn assert_answer(
+    year: u32,
+    day: u32,
+    variant: crate::input::Variant,
+    part: u32,
+    answer: impl fmt::Display,
+) {
+    let key = crate::input::InputKey::new(year, day, variant);
+    let answers = crate::input::InputStore::from_env()
+        .and_then(|store| store.answers(&key))
+        .unwrap();
+    match answers.check(part, &answer.to_string()) {
+        Verdict::Pass => {}
+        Verdict::Fail { expected } => panic!(
+            "{} part {}: expected {}, got {}",
+            key, part, expected, answer
+        ),
+        Verdict::Unknown => panic!("{} part {}: no answer recorded", key, part),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let text = "part 1: 837\npart 2:\n#..#\n####\n#..#\n";
+        let answers = text.parse::<Answers>().unwrap();
+        assert_eq!(Some("837"), answers.get(1));
+        assert_eq!(Some("#..#\n####\n#..#"), answers.get(2));
+        assert_eq!(text, answers.to_string());
+
+        let partial = "part 2: 5353".parse::<Answers>().unwrap();
+        assert_eq!(None, partial.get(1));
+        assert_eq!("part 2: 5353\n", partial.to_string());
+    }
+
+    #[test]
+    fn verdicts() {
+        let answers = "part 1:\n#. \n.#\n\n".parse::<Answers>().unwrap();
+        assert_eq!(Verdict::Pass, answers.check(1, "#.\n.# \n"));
+        assert_eq!(
+            Verdict::Fail {
+                expected: "#.\n.#".to_string()
+            },
+            answers.check(1, ".#\n#.")
+        );
+        assert_eq!(Verdict::Unknown, answers.check(2, "1"));
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            ParseError::unexpected(2, 6, "3", "part 1 or 2"),
+            "part 1: 1\npart 3: 2".parse::<Answers>().unwrap_err()
+        );
+        assert_eq!(
+            ParseError::unexpected(1, 1, "284", "'part <N>: <answer>'"),
+            "284".parse::<Answers>().unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! Timing of the parse and both parts of a solution, and reports of the
+//! results as a table, CSV or JSON.
+
+use super::error::ParseError;
+use super::solution::Solution;
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Times a solution on the raw puzzle input, running the parse and each part
+/// in `parts` the given number of times.
+pub type Bencher = fn(&str, u32, &[u32]) -> Result<Timings, ParseError>;
+
+/// Summary of repeated runs of one stage of a solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: u32,
+    pub min: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    fn of(samples: &[Duration]) -> Stats {
+        let total: Duration = samples.iter().sum();
+        Stats {
+            runs: samples.len() as u32,
+            min: samples.iter().copied().min().unwrap_or_default(),
+            mean: total / (samples.len() as u32).max(1),
+        }
+    }
+}
+
+/// Timings of each stage of a solution. Parts that were not run are `None`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Stats,
+    pub part_1: Option<Stats>,
+    pub part_2: Option<Stats>,
+}
+
+impl Timings {
+    /// Sum of the mean times of every stage that was run.
+    pub fn total(&self) -> Duration {
+        self.parse.mean
+            + self.part_1.map_or(Duration::ZERO, |s| s.mean)
+            + self.part_2.map_or(Duration::ZERO, |s| s.mean)
+    }
+}
+
+/// Times `stage` on a fresh value from `setup` each run, leaving the setup
+/// out of the timing.
+fn sample<I, T>(runs: u32, mut setup: impl FnMut() -> I, mut stage: impl FnMut(I) -> T) -> Stats {
+    let samples = (0..runs.max(1))
+        .map(|_| {
+            let input = setup();
+            let start = Instant::now();
+            black_box(stage(input));
+            start.elapsed()
+        })
+        .collect::<Vec<_>>();
+    Stats::of(&samples)
+}
+
+/// Times the parse and the parts in `parts`. Every run of a part gets its
+/// own parsed input, so work a solution caches in its input is timed each
+/// run instead of only the first.
+pub fn measure<S: Solution>(input: &str, runs: u32, parts: &[u32]) -> Result<Timings, ParseError> {
+    S::parse(input)?;
+    let parsed = || S::parse(input).expect("parsed before");
+    let parse = sample(runs, || input, |input| S::parse(black_box(input)));
+    let part_1 = parts
+        .contains(&1)
+        .then(|| sample(runs, parsed, |parsed| S::part_1(black_box(&parsed))));
+    let part_2 = parts
+        .contains(&2)
+        .then(|| sample(runs, parsed, |parsed| S::part_2(black_box(&parsed))));
+
+    Ok(Timings {
+        parse,
+        part_1,
+        part_2,
+    })
+}
+
+/// The timings of one day on one of its inputs.
+#[derive(Clone, Debug)]
+pub struct Row {
+    pub year: u32,
+    pub day: u32,
+    pub variant: String,
+    pub timings: Timings,
+}
+
+fn duration(stats: Option<Stats>) -> String {
+    stats.map_or("-".to_string(), |s| format!("{:.2?}", s.mean))
+}
+
+/// Mean times of each stage, one row per day with a total at the bottom.
+pub fn table(rows: &[Row]) -> String {
+    let mut table = format!(
+        "{:<6}{:<5}{:<12}{:>12}{:>12}{:>12}{:>12}\n",
+        "year", "day", "variant", "parse", "part 1", "part 2", "total"
+    );
+    for row in rows {
+        let t = &row.timings;
+        let _ = writeln!(
+            table,
+            "{:<6}{:<5}{:<12}{:>12}{:>12}{:>12}{:>12}",
+            row.year,
+            row.day,
+            row.variant,
+            duration(Some(t.parse)),
+            duration(t.part_1),
+            duration(t.part_2),
+            format!("{:.2?}", t.total())
+        );
+    }
+    let total: Duration = rows.iter().map(|r| r.timings.total()).sum();
+    let _ = writeln!(table, "{:<59}{:>12}", "total", format!("{:.2?}", total));
+    table
+}
+
+fn nanos(stats: Option<Stats>) -> String {
+    stats.map_or(String::new(), |s| s.mean.as_nanos().to_string())
+}
+
+/// One line per day with the mean times in nanoseconds. Parts that were not
+/// run are left empty.
+pub fn csv(rows: &[Row]) -> String {
+    let mut csv = String::from("year,day,variant,runs,parse_ns,part_1_ns,part_2_ns\n");
+    for row in rows {
+        let t = &row.timings;
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{}",
+            row.year,
+            row.day,
+            row.variant,
+            t.parse.runs,
+            nanos(Some(t.parse)),
+            nanos(t.part_1),
+            nanos(t.part_2)
+        );
+    }
+    csv
+}
+
+fn json_stats(stats: Option<Stats>) -> String {
+    match stats {
+        Some(s) => format!(
+            "{{\"runs\":{},\"min_ns\":{},\"mean_ns\":{}}}",
+            s.runs,
+            s.min.as_nanos(),
+            s.mean.as_nanos()
+        ),
+        None => "null".to_string(),
+    }
+}
+
+/// An array with one object per day, holding the minimum and mean times of
+/// each stage in nanoseconds, or `null` for parts that were not run.
+pub fn json(rows: &[Row]) -> String {
+    let rows = rows
+        .iter()
+        .map(|row| {
+            let t = &row.timings;
+            format!(
+                "{{\"year\":{},\"day\":{},\"variant\":\"{}\",\"parse\":{},\"part_1\":{},\"part_2\":{}}}",
+                row.year,
+                row.day,
+                row.variant,
+                json_stats(Some(t.parse)),
+                json_stats(t.part_1),
+                json_stats(t.part_2)
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("[\n  {}\n]\n", rows.join(",\n  "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::year_2021::day_1::Day1;
+
+    fn stats(micros: u64) -> Stats {
+        Stats {
+            runs: 2,
+            min: Duration::from_micros(micros / 2),
+            mean: Duration::from_micros(micros),
+        }
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![Row {
+            year: 2021,
+            day: 1,
+            variant: "real".to_string(),
+            timings: Timings {
+                parse: stats(10),
+                part_1: Some(stats(20)),
+                part_2: None,
+            },
+        }]
+    }
+
+    #[test]
+    fn measure_selected_parts() {
+        let timings = measure::<Day1>("1\n2\n3\n4", 3, &[2]).unwrap();
+        assert_eq!(3, timings.parse.runs);
+        assert!(timings.parse.min <= timings.parse.mean);
+        assert_eq!(None, timings.part_1);
+        assert_eq!(Some(3), timings.part_2.map(|s| s.runs));
+
+        assert!(measure::<Day1>("1\nx", 3, &[1, 2]).is_err());
+    }
+
+    #[test]
+    fn reports() {
+        let table = table(&rows());
+        assert_eq!(3, table.lines().count());
+        assert!(table.lines().nth(1).unwrap().starts_with("2021  1    real"));
+        assert!(table.lines().nth(2).unwrap().ends_with("30.00µs"));
+
+        assert_eq!(
+            "year,day,variant,runs,parse_ns,part_1_ns,part_2_ns\n2021,1,real,2,10000,20000,\n",
+            csv(&rows())
+        );
+        assert_eq!(
+            "[\n  {\"year\":2021,\"day\":1,\"variant\":\"real\",\
+             \"parse\":{\"runs\":2,\"min_ns\":5000,\"mean_ns\":10000},\
+             \"part_1\":{\"runs\":2,\"min_ns\":10000,\"mean_ns\":20000},\
+             \"part_2\":null}\n]\n",
+            json(&rows())
+        );
+    }
+}
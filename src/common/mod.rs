@@ -0,0 +1,417 @@
+pub mod search;
+
+use super::error::{source_lines, ParseError};
+use std::ops::{Add, Index, IndexMut, Neg, Sub};
+
+const NEIGHBOR_OFFSETS: [(i32, i32); 8] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (-1, 1),
+    (1, 1),
+    (1, -1),
+];
+
+/// How positions past the edge of a grid are treated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Topology<T> {
+    /// Positions past the edge do not exist.
+    Clipped,
+    /// Positions past the edge wrap around to the opposite side.
+    Toroidal,
+    /// The grid extends forever and every position past the edge holds the given value.
+    Infinite(T),
+}
+
+/// A rectangular grid of cells stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    pub width: usize,
+    pub height: usize,
+
+    pub cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(width * height, cells.len(), "grid cell count mismatch");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Builds a grid from lines of text, mapping every character to a cell with
+    /// `cell`. Characters it rejects are reported as not being `expected`.
+    pub fn parse(
+        s: &str,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut cells = vec![];
+        let mut width = None;
+        for line in source_lines(s) {
+            let mut line_width = 0;
+            for (i, c) in line.text.char_indices() {
+                let value =
+                    cell(c).ok_or_else(|| line.error(&line.text[i..i + c.len_utf8()], expected))?;
+                cells.push(value);
+                line_width += 1;
+            }
+
+            match width {
+                None => width = Some(line_width),
+                Some(w) if w != line_width => {
+                    return Err(line.error(line.text, format!("{} cells", w)));
+                }
+                _ => {}
+            }
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                width,
+                height: cells.len() / width,
+                cells,
+            }),
+            _ => Err(ParseError::missing(1, expected)),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn position(&self, index: usize) -> (i32, i32) {
+        ((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    pub fn index(&self, pos: &(i32, i32)) -> usize {
+        pos.1 as usize * self.width + pos.0 as usize
+    }
+
+    pub fn contains(&self, pos: &(i32, i32)) -> bool {
+        pos.0 >= 0 && pos.1 >= 0 && pos.0 < self.width as i32 && pos.1 < self.height as i32
+    }
+
+    /// Index of `pos`, or `None` if it lies outside the grid.
+    pub fn checked_index(&self, pos: &(i32, i32)) -> Option<usize> {
+        if self.contains(pos) {
+            Some(self.index(pos))
+        } else {
+            None
+        }
+    }
+
+    /// Index of the cell `pos` lands on under `topology`, if it lands on one.
+    pub fn resolve(&self, pos: &(i32, i32), topology: &Topology<T>) -> Option<usize> {
+        match topology {
+            Topology::Clipped | Topology::Infinite(_) => self.checked_index(pos),
+            Topology::Toroidal => Some(self.index(&(
+                pos.0.rem_euclid(self.width as i32),
+                pos.1.rem_euclid(self.height as i32),
+            ))),
+        }
+    }
+
+    /// Value at `pos` under `topology`, including the default value of an infinite grid.
+    pub fn value_at<'a>(&'a self, pos: &(i32, i32), topology: &'a Topology<T>) -> Option<&'a T> {
+        match (self.resolve(pos, topology), topology) {
+            (Some(index), _) => Some(&self.cells[index]),
+            (None, Topology::Infinite(default)) => Some(default),
+            (None, _) => None,
+        }
+    }
+
+    /// Index of the cell `offset` away from `index` under `topology`, if it lands on one.
+    pub fn step(&self, index: usize, offset: (i32, i32), topology: &Topology<T>) -> Option<usize> {
+        let pos = self.position(index);
+        self.resolve(&(pos.0 + offset.0, pos.1 + offset.1), topology)
+    }
+
+    pub fn get(&self, pos: &(i32, i32)) -> Option<&T> {
+        self.checked_index(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: &(i32, i32)) -> Option<&mut T> {
+        self.checked_index(pos).map(move |i| &mut self.cells[i])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Positions next to `pos` under `topology`: clipped drops the ones past the
+    /// edge, toroidal wraps them around and infinite keeps them as they are.
+    pub fn neighbor_positions(
+        &self,
+        pos: &(i32, i32),
+        with_diagonals: bool,
+        topology: &Topology<T>,
+    ) -> Vec<(i32, i32)> {
+        NEIGHBOR_OFFSETS
+            .iter()
+            .take(if with_diagonals { 8 } else { 4 })
+            .map(|n| (n.0 + pos.0, n.1 + pos.1))
+            .filter_map(|p| match topology {
+                Topology::Clipped => self.checked_index(&p).map(|_| p),
+                Topology::Toroidal => self.resolve(&p, topology).map(|i| self.position(i)),
+                Topology::Infinite(_) => Some(p),
+            })
+            .collect()
+    }
+
+    /// Indices of the cells next to `index` under `topology`.
+    pub fn neighbors_in(
+        &self,
+        index: usize,
+        with_diagonals: bool,
+        topology: &Topology<T>,
+    ) -> Vec<usize> {
+        NEIGHBOR_OFFSETS
+            .iter()
+            .take(if with_diagonals { 8 } else { 4 })
+            .filter_map(|&offset| self.step(index, offset, topology))
+            .collect()
+    }
+
+    pub fn neighbors(&self, index: usize, with_diagonals: bool) -> Vec<usize> {
+        self.neighbors_in(index, with_diagonals, &Topology::Clipped)
+    }
+}
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.cells[index]
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.cells[index]
+    }
+}
+
+impl std::str::FromStr for Grid<u32> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, "digit", |c| c.to_digit(10))
+    }
+}
+
+/// A point, or offset between points, in 3D space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub fn new(x: i64, y: i64, z: i64) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan_distance(&self, other: &Point3) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// Multiplies the point by a rotation matrix given as rows.
+    pub fn rotate(&self, matrix: &[[i64; 3]; 3]) -> Point3 {
+        let [x, y, z] = matrix.map(|row| row[0] * self.x + row[1] * self.y + row[2] * self.z);
+        Point3 { x, y, z }
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+
+    fn neg(self) -> Point3 {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// The 24 rotation matrices that turn an axis-aligned object to face any
+/// direction with any side up.
+pub fn rotations() -> Vec<[[i64; 3]; 3]> {
+    const AXES: [[usize; 3]; 6] = [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ];
+
+    let mut matrices = vec![];
+    for axes in AXES.iter() {
+        for signs in 0..8 {
+            let mut matrix = [[0; 3]; 3];
+            for (row, &axis) in axes.iter().enumerate() {
+                matrix[row][axis] = if signs & (1 << row) == 0 { 1 } else { -1 };
+            }
+            if determinant(&matrix) == 1 {
+                matrices.push(matrix);
+            }
+        }
+    }
+    matrices
+}
+
+fn determinant(m: &[[i64; 3]; 3]) -> i64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = "123\n456".parse::<Grid<u32>>().unwrap();
+
+        assert_eq!((3, 2), (grid.width, grid.height));
+        assert_eq!(
+            vec![&[1, 2, 3][..], &[4, 5, 6][..]],
+            grid.rows().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]],
+            grid.columns()
+                .map(|c| c.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn bounds_checked_access() {
+        let mut grid = "123\n456".parse::<Grid<u32>>().unwrap();
+
+        assert_eq!(Some(&6), grid.get(&(2, 1)));
+        assert_eq!(None, grid.get(&(3, 1)));
+        assert_eq!(None, grid.get(&(0, -1)));
+
+        *grid.get_mut(&(0, 0)).unwrap() = 9;
+        assert_eq!(9, grid[0]);
+        assert!(grid.get_mut(&(0, 2)).is_none());
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::filled(3, 3, 0);
+
+        assert_eq!(vec![1, 3], grid.neighbors(0, false));
+        assert_eq!(vec![1, 3, 4], {
+            let mut n = grid.neighbors(0, true);
+            n.sort_unstable();
+            n
+        });
+        assert_eq!(8, grid.neighbors(4, true).len());
+    }
+
+    #[test]
+    fn toroidal_neighbors() {
+        let grid = Grid::filled(3, 3, 0);
+
+        let mut n = grid.neighbors_in(0, false, &Topology::Toroidal);
+        n.sort_unstable();
+        assert_eq!(vec![1, 2, 3, 6], n);
+        assert_eq!(8, grid.neighbors_in(0, true, &Topology::Toroidal).len());
+        assert_eq!(Some(0), grid.step(2, (1, 0), &Topology::Toroidal));
+        assert_eq!(None, grid.step(2, (1, 0), &Topology::Clipped));
+        assert_eq!(
+            vec![(2, 0), (1, 0), (0, 2), (0, 1)],
+            grid.neighbor_positions(&(0, 0), false, &Topology::Toroidal)
+        );
+    }
+
+    #[test]
+    fn infinite_neighbors() {
+        let grid = "12\n34".parse::<Grid<u32>>().unwrap();
+        let topology = Topology::Infinite(0);
+
+        assert_eq!(
+            vec![(-1, 0), (1, 0), (0, -1), (0, 1)],
+            grid.neighbor_positions(&(0, 0), false, &topology)
+        );
+        assert_eq!(vec![1, 2], grid.neighbors_in(0, false, &topology));
+        assert_eq!(Some(&2), grid.value_at(&(1, 0), &topology));
+        assert_eq!(Some(&0), grid.value_at(&(-5, 9), &topology));
+        assert_eq!(None, grid.value_at(&(-5, 9), &Topology::Clipped));
+        assert_eq!(Some(&4), grid.value_at(&(-1, -1), &Topology::Toroidal));
+    }
+
+    #[test]
+    fn rotations_are_distinct() {
+        let point = Point3::new(1, 2, 3);
+        let rotated = rotations()
+            .iter()
+            .map(|m| point.rotate(m))
+            .collect::<std::collections::HashSet<_>>();
+
+        assert_eq!(24, rotated.len());
+        assert!(rotated
+            .iter()
+            .all(|p| p.manhattan_distance(&Point3::default()) == 6));
+        assert!(rotated.contains(&point));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            ParseError::unexpected(2, 2, "x", "digit"),
+            "123\n4x6".parse::<Grid<u32>>().unwrap_err()
+        );
+        assert_eq!(
+            ParseError::unexpected(2, 1, "45", "3 cells"),
+            "123\n45".parse::<Grid<u32>>().unwrap_err()
+        );
+    }
+}
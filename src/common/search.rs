@@ -0,0 +1,151 @@
+//! Cheapest-path search over any state type, with Dijkstra's algorithm or A*.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// The cheapest route to a goal: its total cost and every state along it,
+/// from the start to the goal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<S> {
+    pub cost: usize,
+    pub states: Vec<S>,
+}
+
+/// Finds the cheapest path from `start` to a state accepted by `is_goal`.
+/// `neighbors` yields each state reachable in one step with the cost of
+/// that step.
+pub fn dijkstra<S, I>(
+    start: S,
+    neighbors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    astar(start, neighbors, |_| 0, is_goal)
+}
+
+/// Like `dijkstra`, but explores the states with the lowest cost plus
+/// `heuristic` first. The heuristic must never overestimate the remaining
+/// cost to a goal or the path found may not be the cheapest.
+pub fn astar<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    // states are numbered in the order they are found, so the heap and the
+    // parent links don't need to clone or order them
+    let mut states = vec![start.clone()];
+    let mut ids = HashMap::new();
+    ids.insert(start, 0);
+    // cheapest known cost and predecessor of every state found so far
+    let mut best = vec![(0, None)];
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse((heuristic(&states[0]), 0, 0)));
+
+    while let Some(Reverse((_, cost, id))) = heap.pop() {
+        if cost > best[id].0 {
+            continue;
+        }
+        if is_goal(&states[id]) {
+            let mut path = vec![id];
+            while let Some(parent) = best[path[path.len() - 1]].1 {
+                path.push(parent);
+            }
+            return Some(Path {
+                cost,
+                states: path
+                    .into_iter()
+                    .rev()
+                    .map(|id| states[id].clone())
+                    .collect(),
+            });
+        }
+
+        for (next, step) in neighbors(&states[id]) {
+            let next_cost = cost + step;
+            let next_id = match ids.entry(next) {
+                Entry::Occupied(entry) => {
+                    let next_id = *entry.get();
+                    if next_cost >= best[next_id].0 {
+                        continue;
+                    }
+                    best[next_id] = (next_cost, Some(id));
+                    next_id
+                }
+                Entry::Vacant(entry) => {
+                    states.push(entry.key().clone());
+                    best.push((next_cost, Some(id)));
+                    *entry.insert(states.len() - 1)
+                }
+            };
+            let priority = next_cost + heuristic(&states[next_id]);
+            heap.push(Reverse((priority, next_cost, next_id)));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Steps along a line of `len` positions, where moving right costs 1
+    /// and moving left costs 3, with a shortcut from 0 to 5 costing 10.
+    fn line(len: i32) -> impl FnMut(&i32) -> Vec<(i32, usize)> {
+        move |&p| {
+            let mut next = vec![(p + 1, 1), (p - 1, 3)];
+            if p == 0 {
+                next.push((5, 10));
+            }
+            next.into_iter()
+                .filter(|&(n, _)| (0..len).contains(&n))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn cheapest_path() {
+        let path = dijkstra(0, line(10), |&p| p == 5).unwrap();
+        assert_eq!(5, path.cost);
+        assert_eq!(vec![0, 1, 2, 3, 4, 5], path.states);
+
+        let path = dijkstra(6, line(10), |&p| p == 4).unwrap();
+        assert_eq!(6, path.cost);
+        assert_eq!(vec![6, 5, 4], path.states);
+
+        let path = dijkstra(3, line(10), |&p| p == 3).unwrap();
+        assert_eq!(0, path.cost);
+        assert_eq!(vec![3], path.states);
+    }
+
+    #[test]
+    fn unreachable() {
+        assert_eq!(None, dijkstra(0, line(10), |&p| p == 10));
+    }
+
+    #[test]
+    fn heuristic() {
+        let mut explored = 0;
+        let counted = |p: &i32| {
+            explored += 1;
+            line(100)(p)
+        };
+        let path = astar(
+            0,
+            counted,
+            |&p| (50 - p).unsigned_abs() as usize,
+            |&p| p == 50,
+        );
+        assert_eq!(50, path.unwrap().cost);
+        assert_eq!(50, explored);
+    }
+}
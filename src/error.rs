@@ -0,0 +1,202 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Error returned when puzzle input does not match the expected format.
+///
+/// Lines and columns are 1-based so they can be matched against an editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// `found` was read where `expected` should have been.
+    Unexpected {
+        line: usize,
+        column: usize,
+        found: String,
+        expected: String,
+    },
+    /// The input ended before `expected` was read.
+    Missing { line: usize, expected: String },
+}
+
+impl ParseError {
+    pub fn unexpected(
+        line: usize,
+        column: usize,
+        found: impl Into<String>,
+        expected: impl Into<String>,
+    ) -> ParseError {
+        ParseError::Unexpected {
+            line,
+            column,
+            found: found.into(),
+            expected: expected.into(),
+        }
+    }
+
+    pub fn missing(line: usize, expected: impl Into<String>) -> ParseError {
+        ParseError::Missing {
+            line,
+            expected: expected.into(),
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        match self {
+            ParseError::Unexpected { line, .. } | ParseError::Missing { line, .. } => *line,
+        }
+    }
+
+    /// Moves the error down by `lines`, for errors from a parser that was handed
+    /// a single line out of a larger input.
+    pub fn offset_lines(self, lines: usize) -> ParseError {
+        match self {
+            ParseError::Unexpected {
+                line,
+                column,
+                found,
+                expected,
+            } => ParseError::Unexpected {
+                line: line + lines,
+                column,
+                found,
+                expected,
+            },
+            ParseError::Missing { line, expected } => ParseError::Missing {
+                line: line + lines,
+                expected,
+            },
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Unexpected {
+                line,
+                column,
+                found,
+                expected,
+            } => write!(
+                f,
+                "line {}, column {}: expected {}, found '{}'",
+                line, column, expected, found
+            ),
+            ParseError::Missing { line, expected } => {
+                write!(
+                    f,
+                    "line {}: expected {}, found end of input",
+                    line, expected
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A numbered line of puzzle input that parse errors can point into.
+#[derive(Clone, Copy)]
+pub struct SourceLine<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> SourceLine<'a> {
+    pub fn new(number: usize, text: &'a str) -> SourceLine<'a> {
+        SourceLine { number, text }
+    }
+
+    /// Column of `token`, which must be a slice of this line's text.
+    pub fn column(&self, token: &str) -> usize {
+        let offset = (token.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+        if offset <= self.text.len() {
+            self.text[..offset].chars().count() + 1
+        } else {
+            1
+        }
+    }
+
+    pub fn error(&self, token: &str, expected: impl Into<String>) -> ParseError {
+        if token.is_empty() && self.column(token) > self.text.chars().count() {
+            ParseError::missing(self.number, expected)
+        } else {
+            ParseError::unexpected(self.number, self.column(token), token, expected)
+        }
+    }
+
+    /// Parses `token`, a slice of this line, reporting its position on failure.
+    pub fn parse<T: FromStr>(&self, token: &str, expected: &str) -> Result<T, ParseError> {
+        token
+            .trim()
+            .parse::<T>()
+            .map_err(|_| self.error(token.trim(), expected))
+    }
+
+    pub fn split_once(&self, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text
+            .split_once(delimiter)
+            .ok_or_else(|| self.error(self.text, format!("'{}'", delimiter)))
+    }
+}
+
+/// Iterates over the lines of `input`, numbered from 1.
+pub fn source_lines(input: &str) -> impl Iterator<Item = SourceLine<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, text)| SourceLine::new(i + 1, text))
+}
+
+/// Parses a comma separated list of numbers on the first line of `input`.
+pub fn parse_comma_separated<T: FromStr>(
+    input: &str,
+    expected: &str,
+) -> Result<Vec<T>, ParseError> {
+    let line = SourceLine::new(1, input.lines().next().unwrap_or_default());
+    line.text
+        .split(',')
+        .map(|token| line.parse::<T>(token, expected))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_position_of_bad_token() {
+        let line = SourceLine::new(3, "forward x5");
+        let token = line.text.split_whitespace().nth(1).unwrap();
+        let error = line.parse::<u32>(token, "distance").unwrap_err();
+
+        assert_eq!(ParseError::unexpected(3, 9, "x5", "distance"), error);
+        assert_eq!(
+            "line 3, column 9: expected distance, found 'x5'",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn reports_missing_token_at_end_of_line() {
+        let line = SourceLine::new(1, "a-");
+        let (_, b) = line.split_once("-").unwrap();
+
+        assert_eq!(ParseError::missing(1, "cave"), line.error(b, "cave"));
+        assert_eq!(
+            ParseError::missing(5, "cave"),
+            line.error(b, "cave").offset_lines(4)
+        );
+    }
+
+    #[test]
+    fn comma_separated() {
+        assert_eq!(
+            vec![3, 4, 3],
+            parse_comma_separated::<u8>("3,4,3\n", "age").unwrap()
+        );
+        assert_eq!(
+            ParseError::unexpected(1, 5, "x", "age"),
+            parse_comma_separated::<u8>("3,4,x", "age").unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,470 @@
+use super::answers::{self, Answers};
+use super::error::{source_lines, ParseError, SourceLine};
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the directory of the input store.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Environment variable naming the session whose real inputs are used.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Directory of the input store when `AOC_INPUT_DIR` is not set.
+pub const DEFAULT_INPUT_DIR: &str = "input";
+
+/// Year that puzzles are run for when none is given.
+pub const DEFAULT_YEAR: u32 = 2021;
+
+/// Name of the manifest file in the root of an input store.
+pub const MANIFEST: &str = "manifest.txt";
+
+/// Which of the inputs for a day to load.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Variant {
+    /// The personal puzzle input.
+    Real,
+    /// The n-th example from the puzzle text, counting from 1.
+    Example(u32),
+    /// Any other input saved for the day, such as `example_1_step`.
+    Named(String),
+}
+
+impl Variant {
+    pub fn named(name: &str) -> Variant {
+        Variant::Named(name.to_string())
+    }
+
+    /// The file name inputs of this variant are saved under for `day`.
+    pub fn file_name(&self, day: u32) -> String {
+        match self {
+            Variant::Real => format!("day_{}.txt", day),
+            Variant::Example(1) => format!("day_{}_example.txt", day),
+            Variant::Example(n) => format!("day_{}_example_{}.txt", day, n),
+            Variant::Named(name) => format!("day_{}_{}.txt", day, name),
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Variant::Real => write!(f, "real"),
+            Variant::Example(1) => write!(f, "example"),
+            Variant::Example(n) => write!(f, "example_{}", n),
+            Variant::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl std::str::FromStr for Variant {
+    type Err = String;
+
+    /// Parses the names printed by `Display`: `real`, `example`, `example_N`
+    /// or any other name made of letters, digits and underscores.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("'{}' is not a valid input variant", s));
+        }
+
+        let example = s
+            .strip_prefix("example_")
+            .and_then(|n| n.parse::<u32>().ok());
+        Ok(match (s, example) {
+            ("real", _) => Variant::Real,
+            ("example", _) => Variant::Example(1),
+            (_, Some(n)) if n > 0 => Variant::Example(n),
+            _ => Variant::named(s),
+        })
+    }
+}
+
+/// Identifies one input in a store.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct InputKey {
+    pub year: u32,
+    pub day: u32,
+    pub variant: Variant,
+}
+
+impl InputKey {
+    pub fn new(year: u32, day: u32, variant: Variant) -> InputKey {
+        InputKey { year, day, variant }
+    }
+}
+
+impl fmt::Display for InputKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}/{}", self.year, self.day, self.variant)
+    }
+}
+
+/// A key in the manifest: real inputs may belong to a session, examples are
+/// shared by everyone.
+type Entry = (InputKey, Option<String>);
+
+fn parse_entry(line: &SourceLine) -> Result<(Entry, PathBuf), ParseError> {
+    let (key, path) = line.split_once("=")?;
+    let (key, session) = match key.trim().split_once('@') {
+        Some((key, session)) => (key, Some(session)),
+        None => (key.trim(), None),
+    };
+
+    let fields = key.split('/').collect::<Vec<_>>();
+    if fields.len() != 3 {
+        return Err(line.error(key, "'<year>/<day>/<variant>'"));
+    }
+    let year = line.parse::<u32>(fields[0], "year")?;
+    let day = line.parse::<u32>(fields[1], "day")?;
+    let variant = fields[2]
+        .parse::<Variant>()
+        .map_err(|_| line.error(fields[2], "variant"))?;
+    if let Some(session) = session {
+        if !is_valid_session(session) || variant != Variant::Real {
+            return Err(line.error(session, "session of a real input"));
+        }
+    }
+
+    let path = path.trim();
+    if path.is_empty() {
+        return Err(line.error(path, "path"));
+    }
+
+    let key = InputKey::new(year, day, variant);
+    Ok(((key, session.map(str::to_string)), PathBuf::from(path)))
+}
+
+/// Failure to find, read or save an input.
+#[derive(Debug)]
+pub enum InputError {
+    Read { path: PathBuf, source: io::Error },
+    Write { path: PathBuf, source: io::Error },
+    Manifest { path: PathBuf, error: ParseError },
+    Answers { path: PathBuf, error: ParseError },
+    NotFound { key: InputKey },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Read { path, source } => {
+                write!(f, "failed to read '{}': {}", path.display(), source)
+            }
+            InputError::Write { path, source } => {
+                write!(f, "failed to write '{}': {}", path.display(), source)
+            }
+            InputError::Manifest { path, error } | InputError::Answers { path, error } => {
+                write!(f, "{}: {}", path.display(), error)
+            }
+            InputError::NotFound { key } => write!(f, "no input {} in the manifest", key),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Read { source, .. } | InputError::Write { source, .. } => Some(source),
+            InputError::Manifest { error, .. } | InputError::Answers { error, .. } => Some(error),
+            InputError::NotFound { .. } => None,
+        }
+    }
+}
+
+/// Reads the input at `path`.
+pub fn read(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|source| InputError::Read {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Puzzle inputs kept in a local directory and listed in its manifest.
+///
+/// The manifest maps `<year>/<day>/<variant>[@<session>]` to a path relative
+/// to the directory. Real inputs of the store's session are looked up before
+/// the ones without a session, so a team can share one store.
+#[derive(Clone, Debug)]
+pub struct InputStore {
+    root: PathBuf,
+    session: Option<String>,
+    entries: BTreeMap<Entry, PathBuf>,
+}
+
+impl InputStore {
+    /// Opens the store in `root`. A missing manifest is an empty store.
+    pub fn open(root: impl Into<PathBuf>) -> Result<InputStore, InputError> {
+        let root = root.into();
+        let manifest = root.join(MANIFEST);
+
+        let mut entries = BTreeMap::new();
+        match fs::read_to_string(&manifest) {
+            Ok(text) => {
+                for line in source_lines(&text) {
+                    if line.text.trim().is_empty() || line.text.starts_with('#') {
+                        continue;
+                    }
+                    let (entry, path) =
+                        parse_entry(&line).map_err(|error| InputError::Manifest {
+                            path: manifest.clone(),
+                            error,
+                        })?;
+                    entries.insert(entry, path);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(source) => {
+                return Err(InputError::Read {
+                    path: manifest,
+                    source,
+                })
+            }
+        }
+
+        Ok(InputStore {
+            root,
+            session: None,
+            entries,
+        })
+    }
+
+    /// Opens the store named by `AOC_INPUT_DIR`, or `input` if it is not set,
+    /// for the session named by `AOC_SESSION`.
+    pub fn from_env() -> Result<InputStore, InputError> {
+        let root = env::var_os(INPUT_DIR_VAR).unwrap_or_else(|| DEFAULT_INPUT_DIR.into());
+        Ok(InputStore::open(root)?.with_session(env_session()))
+    }
+
+    pub fn with_session(self, session: Option<String>) -> InputStore {
+        InputStore { session, ..self }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn session(&self) -> Option<&str> {
+        self.session.as_deref()
+    }
+
+    /// Every key in the manifest with the session it belongs to, if any.
+    pub fn keys(&self) -> impl Iterator<Item = (&InputKey, Option<&str>)> {
+        self.entries
+            .keys()
+            .map(|(key, session)| (key, session.as_deref()))
+    }
+
+    /// Path of the input for `key`, preferring the one of this store's session.
+    pub fn path(&self, key: &InputKey) -> Result<PathBuf, InputError> {
+        let own = self
+            .session
+            .as_ref()
+            .and_then(|s| self.entries.get(&(key.clone(), Some(s.clone()))));
+        own.or_else(|| self.entries.get(&(key.clone(), None)))
+            .map(|path| self.root.join(path))
+            .ok_or_else(|| InputError::NotFound { key: key.clone() })
+    }
+
+    pub fn load(&self, key: &InputKey) -> Result<String, InputError> {
+        read(&self.path(key)?)
+    }
+
+    /// The answers recorded next to the input for `key`. A missing answers
+    /// file means every answer is unknown.
+    pub fn answers(&self, key: &InputKey) -> Result<Answers, InputError> {
+        read_answers(&self.path(key)?)
+    }
+
+    /// Records `answers` next to the input for `key`.
+    pub fn save_answers(&self, key: &InputKey, answers: &Answers) -> Result<PathBuf, InputError> {
+        let path = answers::path_for(&self.path(key)?);
+        write(&path, &answers.to_string())?;
+        Ok(path)
+    }
+
+    /// Saves `contents` as the input for `key`, belonging to this store's
+    /// session if it is a real input, and records it in the manifest.
+    pub fn save(&mut self, key: &InputKey, contents: &str) -> Result<PathBuf, InputError> {
+        let session = match key.variant {
+            Variant::Real => self.session.clone(),
+            _ => None,
+        };
+
+        let mut relative = PathBuf::from(key.year.to_string());
+        if let Some(session) = &session {
+            relative.push(session);
+        }
+        relative.push(key.variant.file_name(key.day));
+
+        let path = self.root.join(&relative);
+        write(&path, contents)?;
+
+        self.entries.insert((key.clone(), session), relative);
+        write(&self.root.join(MANIFEST), &self.manifest())?;
+        Ok(path)
+    }
+
+    fn manifest(&self) -> String {
+        let mut manifest =
+            String::from("# <year>/<day>/<variant>[@<session>] = <path relative to this file>\n");
+        for ((key, session), path) in self.entries.iter() {
+            let session = session
+                .as_ref()
+                .map_or(String::new(), |s| format!("@{}", s));
+            manifest += &format!("{}{} = {}\n", key, session, path.display());
+        }
+        manifest
+    }
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), InputError> {
+    path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(path, contents))
+        .map_err(|source| InputError::Write {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+/// Reads the answers recorded for the input at `input`, which are all unknown
+/// if there is no answers file.
+pub fn read_answers(input: &Path) -> Result<Answers, InputError> {
+    let path = answers::path_for(input);
+    match fs::read_to_string(&path) {
+        Ok(text) => text
+            .parse::<Answers>()
+            .map_err(|error| InputError::Answers { path, error }),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(source) => Err(InputError::Read { path, source }),
+    }
+}
+
+/// The session named by `AOC_SESSION`, if it is set.
+pub fn env_session() -> Option<String> {
+    env::var(SESSION_VAR).ok().filter(|s| is_valid_session(s))
+}
+
+/// Session names double as directory names, so they are limited to letters,
+/// digits, `_` and `-`.
+pub fn is_valid_session(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Loads an input from the store configured in the environment.
+pub fn load(year: u32, day: u32, variant: Variant) -> Result<String, InputError> {
+    InputStore::from_env()?.load(&InputKey::new(year, day, variant))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An empty directory under the system temp directory, unique to `name`.
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-input-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn variant_names() {
+        for variant in [
+            Variant::Real,
+            Variant::Example(1),
+            Variant::Example(2),
+            Variant::named("example_1_step"),
+        ] {
+            assert_eq!(Ok(variant.clone()), variant.to_string().parse::<Variant>());
+        }
+        assert!("../day_1".parse::<Variant>().is_err());
+    }
+
+    #[test]
+    fn manifest_entries() {
+        let line = SourceLine::new(1, "2021/22/example_2 = 2021/day_22_example_2.txt");
+        assert_eq!(
+            (
+                (InputKey::new(2021, 22, Variant::Example(2)), None),
+                PathBuf::from("2021/day_22_example_2.txt")
+            ),
+            parse_entry(&line).unwrap()
+        );
+
+        let line = SourceLine::new(1, "2021/1/real@alice = 2021/alice/day_1.txt");
+        assert_eq!(Some("alice".to_string()), parse_entry(&line).unwrap().0 .1);
+
+        let line = SourceLine::new(4, "2021/x/real = day_1.txt");
+        assert_eq!(
+            ParseError::unexpected(4, 6, "x", "day"),
+            parse_entry(&line).unwrap_err()
+        );
+        let line = SourceLine::new(1, "2021/1/example@alice = day_1.txt");
+        assert_eq!(
+            ParseError::unexpected(1, 16, "alice", "session of a real input"),
+            parse_entry(&line).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn sessions() {
+        let dir = scratch_dir("sessions");
+        let real = InputKey::new(2020, 1, Variant::Real);
+        let example = InputKey::new(2020, 1, Variant::Example(1));
+
+        let mut store = InputStore::open(&dir).unwrap();
+        store.save(&real, "shared").unwrap();
+        let mut alice = store.with_session(Some("alice".to_string()));
+        assert_eq!(
+            dir.join("2020/alice/day_1.txt"),
+            alice.save(&real, "alice's").unwrap()
+        );
+        assert_eq!(
+            dir.join("2020/day_1_example.txt"),
+            alice.save(&example, "example").unwrap()
+        );
+
+        // reopening reads everything back from the manifest
+        let store = InputStore::open(&dir).unwrap();
+        assert_eq!("shared", store.load(&real).unwrap());
+        let bob = store.clone().with_session(Some("bob".to_string()));
+        assert_eq!("shared", bob.load(&real).unwrap());
+        let alice = store.with_session(Some("alice".to_string()));
+        assert_eq!("alice's", alice.load(&real).unwrap());
+        assert_eq!("example", alice.load(&example).unwrap());
+
+        let missing = InputKey::new(2020, 2, Variant::Real);
+        assert_eq!(
+            "no input 2020/2/real in the manifest",
+            alice.load(&missing).unwrap_err().to_string()
+        );
+
+        let mut answers = alice.answers(&real).unwrap();
+        assert_eq!(Answers::default(), answers);
+        answers.set(2, "42");
+        assert_eq!(
+            dir.join("2020/alice/day_1.answers"),
+            alice.save_answers(&real, &answers).unwrap()
+        );
+        assert_eq!(answers, alice.answers(&real).unwrap());
+        assert_eq!(Answers::default(), bob.answers(&real).unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn repository_inputs() {
+        let store = InputStore::open(DEFAULT_INPUT_DIR).unwrap();
+        assert!(store.keys().count() > 0);
+        for (key, _) in store.keys() {
+            assert!(store.path(key).unwrap().is_file(), "{} is missing", key);
+            assert!(store.answers(key).is_ok(), "{} has malformed answers", key);
+        }
+    }
+}
@@ -2,29 +2,69 @@
 extern crate lazy_static;
 extern crate itertools;
 
+/// Builds the `Day` entry for a `Solution` type.
+macro_rules! day {
+    ($day:expr, $solution:ty) => {
+        $crate::Day {
+            day: $day,
+            part_1: $crate::solution::run_part_1::<$solution>,
+            part_2: $crate::solution::run_part_2::<$solution>,
+            bench: $crate::bench::measure::<$solution>,
+            render: $crate::solution::run_render::<$solution>,
+        }
+    };
+}
+
+pub mod answers;
+pub mod bench;
 mod common;
-// mod day_1;
-// mod day_2;
-// mod day_3;
-// mod day_4;
-// mod day_5;
-//mod day_6;
-//mod day_7;
-//mod day_8;
-//mod day_9;
-//mod day_10;
-//mod day_11;
-//mod day_12;
-//mod day_13;
-//mod day_14;
-//mod day_15;
-//mod day_16;
-//mod day_17;
-//mod day_18;
-//mod day_19;
-//mod day_20;
-//mod day_21;
-//mod day_22;
-//mod day_23;
-//mod day_24;
-mod day_25;
+mod error;
+pub mod input;
+pub mod solution;
+pub mod year_2021;
+
+pub use error::ParseError;
+pub use solution::Solution;
+
+/// Parses the raw puzzle input and answers one part of a puzzle.
+pub type Runner = fn(&str) -> Result<String, ParseError>;
+
+/// Parses the raw puzzle input and draws how one part is answered, if the
+/// puzzle has a drawing.
+pub type Renderer = fn(&str, u32) -> Result<Option<String>, ParseError>;
+
+/// Type-erased entry points for a single puzzle, each taking the raw puzzle input.
+pub struct Day {
+    pub day: u32,
+    pub part_1: Runner,
+    pub part_2: Runner,
+    pub bench: bench::Bencher,
+    pub render: Renderer,
+}
+
+impl Day {
+    pub fn part(&self, part: u32) -> Option<Runner> {
+        match part {
+            1 => Some(self.part_1),
+            2 => Some(self.part_2),
+            _ => None,
+        }
+    }
+}
+
+/// The solved puzzles of one event year.
+pub struct Year {
+    pub year: u32,
+    pub days: &'static [Day],
+}
+
+/// Every event year with solutions, oldest first.
+pub const YEARS: &[Year] = &[year_2021::YEAR];
+
+pub fn find_year(year: u32) -> Option<&'static Year> {
+    YEARS.iter().find(|y| y.year == year)
+}
+
+pub fn find_day(year: u32, day: u32) -> Option<&'static Day> {
+    find_year(year)?.days.iter().find(|d| d.day == day)
+}
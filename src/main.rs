@@ -0,0 +1,344 @@
+use advent_of_code::answers::Verdict;
+use advent_of_code::bench;
+use advent_of_code::input::{self, InputKey, InputStore, Variant, DEFAULT_YEAR};
+use std::env;
+use std::path::PathBuf;
+use std::process;
+use std::time::Instant;
+
+const USAGE: &str = "usage:
+    aoc run --day <N> [--part <1|2>] [--render] [INPUT] [--input <path>]
+    aoc import --day <N> --file <path> [INPUT]
+    aoc bench [--year <Y>] [--day <N>] [--part <1|2>] [--variant <V>]
+              [--runs <N>] [--format <table|csv|json>]
+              [--input-dir <dir>] [--session <name>]
+    aoc verify [--year <Y>] [--day <N>] [--part <1|2>] [--variant <V>]
+               [--input-dir <dir>] [--session <name>]
+    aoc list
+
+INPUT selects an input from the store:
+    [--year <Y>] [--variant <real|example|example_N|name>]
+    [--input-dir <dir>] [--session <name>]
+
+The store defaults to $AOC_INPUT_DIR or 'input' and the session to $AOC_SESSION.
+--input reads the given file instead of looking it up.
+--render also prints how each part is answered, for days that can show it.
+Answers are checked against the '.answers' file next to each input.
+verify checks every input in the store with recorded answers.
+bench times the parse and each part of every day with an input of the
+variant (real by default), 5 runs each.";
+
+#[derive(Default)]
+struct Args {
+    year: Option<u32>,
+    day: Option<u32>,
+    part: Option<u32>,
+    variant: Option<Variant>,
+    input_dir: Option<PathBuf>,
+    session: Option<String>,
+    input: Option<PathBuf>,
+    file: Option<PathBuf>,
+    runs: Option<u32>,
+    format: Option<String>,
+    render: bool,
+}
+
+impl Args {
+    fn key(&self) -> Result<InputKey, String> {
+        Ok(InputKey::new(
+            self.year.unwrap_or(DEFAULT_YEAR),
+            self.day.ok_or("--day is required")?,
+            self.variant.clone().unwrap_or(Variant::Real),
+        ))
+    }
+
+    fn store(&self) -> Result<InputStore, String> {
+        let store = match &self.input_dir {
+            Some(dir) => InputStore::open(dir).map(|s| s.with_session(input::env_session())),
+            None => InputStore::from_env(),
+        }
+        .map_err(|e| e.to_string())?;
+
+        Ok(match &self.session {
+            Some(session) => store.with_session(Some(session.clone())),
+            None => store,
+        })
+    }
+}
+
+fn parse_number(value: &str, name: &str) -> Result<u32, String> {
+    value
+        .parse::<u32>()
+        .map_err(|_| format!("'{}' is not a valid {}", value, name))
+}
+
+/// Parses `--flag value` pairs and the `--render` switch, accepting only the
+/// flags in `allowed`.
+fn parse_args(args: &[String], allowed: &[&str]) -> Result<Args, String> {
+    let mut parsed = Args::default();
+
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        if !allowed.contains(&flag.as_str()) {
+            return Err(format!("unknown argument '{}'", flag));
+        }
+        if flag == "--render" {
+            parsed.render = true;
+            continue;
+        }
+        let value = args.next().ok_or(format!("missing value for '{}'", flag))?;
+        match flag.as_str() {
+            "--year" => parsed.year = Some(parse_number(value, "year")?),
+            "--day" => parsed.day = Some(parse_number(value, "day")?),
+            "--part" => parsed.part = Some(parse_number(value, "part")?),
+            "--variant" => parsed.variant = Some(value.parse::<Variant>()?),
+            "--input-dir" => parsed.input_dir = Some(PathBuf::from(value)),
+            "--session" => {
+                if !input::is_valid_session(value) {
+                    return Err(format!("'{}' is not a valid session", value));
+                }
+                parsed.session = Some(value.clone())
+            }
+            "--input" => parsed.input = Some(PathBuf::from(value)),
+            "--runs" => parsed.runs = Some(parse_number(value, "number of runs")?),
+            "--format" => match value.as_str() {
+                "table" | "csv" | "json" => parsed.format = Some(value.clone()),
+                _ => return Err(format!("'{}' is not a valid format", value)),
+            },
+            _ => parsed.file = Some(PathBuf::from(value)),
+        }
+    }
+
+    Ok(parsed)
+}
+
+const INPUT_FLAGS: [&str; 5] = ["--year", "--day", "--variant", "--input-dir", "--session"];
+
+fn run(args: &Args) -> Result<(), String> {
+    let key = args.key()?;
+    let year = advent_of_code::find_year(key.year)
+        .ok_or(format!("year {} has no registered solutions", key.year))?;
+    let day = advent_of_code::find_day(key.year, key.day).ok_or(format!(
+        "day {} of {} has no registered solution",
+        key.day, year.year
+    ))?;
+
+    let path = match &args.input {
+        Some(path) => path.clone(),
+        None => args.store()?.path(&key).map_err(|e| e.to_string())?,
+    };
+    let input = input::read(&path).map_err(|e| e.to_string())?;
+    let answers = input::read_answers(&path).map_err(|e| e.to_string())?;
+
+    let parts = args.part.map_or(vec![1, 2], |p| vec![p]);
+    for part in parts {
+        let solve = day
+            .part(part)
+            .ok_or(format!("'{}' is not a valid part", part))?;
+
+        let start = Instant::now();
+        let answer = solve(&input).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let elapsed = start.elapsed();
+
+        // multi-line answers (rendered letters) start on their own line
+        let separator = if answer.contains('\n') { "\n" } else { " " };
+        let verdict = match answers.check(part, &answer) {
+            Verdict::Fail { expected } => format!("fail, expected {}", expected),
+            verdict => verdict.to_string(),
+        };
+        println!(
+            "{} day {} part {}:{}{} ({:.2?}) [{}]",
+            year.year, day.day, part, separator, answer, elapsed, verdict
+        );
+
+        if args.render {
+            let drawing = (day.render)(&input, part)
+                .map_err(|e| format!("{}: {}", path.display(), e))?
+                .ok_or(format!(
+                    "day {} part {} has nothing to render",
+                    day.day, part
+                ))?;
+            println!("{}", drawing.trim_end());
+        }
+    }
+
+    Ok(())
+}
+
+fn import(args: &Args) -> Result<(), String> {
+    let key = args.key()?;
+    let file = args.file.as_ref().ok_or("--file is required")?;
+    let contents = input::read(file).map_err(|e| e.to_string())?;
+
+    let mut store = args.store()?;
+    let path = store.save(&key, &contents).map_err(|e| e.to_string())?;
+    match store.session() {
+        Some(session) if key.variant == Variant::Real => {
+            println!("Saved {}@{} to {}", key, session, path.display())
+        }
+        _ => println!("Saved {} to {}", key, path.display()),
+    }
+
+    Ok(())
+}
+
+/// Runs every input in the store that has a registered solution and prints
+/// whether each part matches the recorded answer. Parts without a recorded
+/// answer are not run. Fails if any part does not match.
+fn verify(args: &Args) -> Result<(), String> {
+    let store = args.store()?;
+    let mut keys = store
+        .keys()
+        .filter(|(_, session)| session.is_none() || *session == store.session())
+        .map(|(key, _)| key.clone())
+        .filter(|key| args.year.is_none_or(|y| key.year == y))
+        .filter(|key| args.day.is_none_or(|d| key.day == d))
+        .filter(|key| args.variant.as_ref().is_none_or(|v| key.variant == *v))
+        .collect::<Vec<_>>();
+    keys.dedup();
+
+    let parts = args.part.map_or(vec![1, 2], |p| vec![p]);
+    let header = parts
+        .iter()
+        .map(|p| format!("{:<9}", format!("part {}", p)))
+        .collect::<String>();
+    println!(
+        "{:<6}{:<5}{:<16}{}",
+        "year",
+        "day",
+        "variant",
+        header.trim_end()
+    );
+
+    let mut failures = Vec::new();
+    for key in keys {
+        let day = match advent_of_code::find_day(key.year, key.day) {
+            Some(day) => day,
+            None => continue,
+        };
+        let path = store.path(&key).map_err(|e| e.to_string())?;
+        let input = input::read(&path).map_err(|e| e.to_string())?;
+        let answers = input::read_answers(&path).map_err(|e| e.to_string())?;
+
+        let mut row = format!(
+            "{:<6}{:<5}{:<16}",
+            key.year,
+            key.day,
+            key.variant.to_string()
+        );
+        for &part in &parts {
+            let solve = day
+                .part(part)
+                .ok_or(format!("'{}' is not a valid part", part))?;
+            let verdict = match answers.get(part).map(|_| solve(&input)) {
+                None => Verdict::Unknown.to_string(),
+                Some(Ok(answer)) => match answers.check(part, &answer) {
+                    Verdict::Fail { expected } => {
+                        failures.push(format!(
+                            "{} part {}: expected {}, got {}",
+                            key, part, expected, answer
+                        ));
+                        "fail".to_string()
+                    }
+                    verdict => verdict.to_string(),
+                },
+                Some(Err(e)) => {
+                    failures.push(format!("{} part {}: {}", key, part, e));
+                    "error".to_string()
+                }
+            };
+            row += &format!("{:<9}", verdict);
+        }
+        println!("{}", row.trim_end());
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("\n"))
+    }
+}
+
+/// Times every registered day that has an input of the requested variant and
+/// prints the results in the requested format.
+fn bench(args: &Args) -> Result<(), String> {
+    let store = args.store()?;
+    let variant = args.variant.clone().unwrap_or(Variant::Real);
+    let parts = args.part.map_or(vec![1, 2], |p| vec![p]);
+    if parts.iter().any(|&p| p != 1 && p != 2) {
+        return Err(format!("'{}' is not a valid part", parts[0]));
+    }
+
+    let mut rows = Vec::new();
+    for year in advent_of_code::YEARS {
+        if args.year.is_some_and(|y| y != year.year) {
+            continue;
+        }
+        for day in year.days {
+            if args.day.is_some_and(|d| d != day.day) {
+                continue;
+            }
+            let key = InputKey::new(year.year, day.day, variant.clone());
+            let path = match store.path(&key) {
+                Ok(path) => path,
+                Err(_) => continue,
+            };
+            let input = input::read(&path).map_err(|e| e.to_string())?;
+
+            let timings = (day.bench)(&input, args.runs.unwrap_or(5), &parts)
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+            rows.push(bench::Row {
+                year: year.year,
+                day: day.day,
+                variant: variant.to_string(),
+                timings,
+            });
+        }
+    }
+
+    let report = match args.format.as_deref() {
+        Some("csv") => bench::csv(&rows),
+        Some("json") => bench::json(&rows),
+        _ => bench::table(&rows),
+    };
+    print!("{}", report);
+
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(|s| s.as_str()) {
+        Some("run") => {
+            let allowed = [&INPUT_FLAGS[..], &["--part", "--input", "--render"]].concat();
+            parse_args(&args[1..], &allowed).and_then(|a| run(&a))
+        }
+        Some("import") => {
+            let allowed = [&INPUT_FLAGS[..], &["--file"]].concat();
+            parse_args(&args[1..], &allowed).and_then(|a| import(&a))
+        }
+        Some("bench") => {
+            let allowed = [&INPUT_FLAGS[..], &["--part", "--runs", "--format"]].concat();
+            parse_args(&args[1..], &allowed).and_then(|a| bench(&a))
+        }
+        Some("verify") => {
+            let allowed = [&INPUT_FLAGS[..], &["--part"]].concat();
+            parse_args(&args[1..], &allowed).and_then(|a| verify(&a))
+        }
+        Some("list") => {
+            for year in advent_of_code::YEARS {
+                for day in year.days {
+                    println!("{} {}", year.year, day.day);
+                }
+            }
+            Ok(())
+        }
+        _ => Err(USAGE.to_string()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,31 @@
+use super::error::ParseError;
+use std::fmt::Display;
+
+/// A single day's puzzle: how to read the input and how to answer both parts.
+pub trait Solution {
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_1(input: &Self::Input) -> Self::Answer1;
+    fn part_2(input: &Self::Input) -> Self::Answer2;
+
+    /// A drawing or explanation of how `part` is answered, for puzzles that
+    /// have something worth showing besides the answer.
+    fn render(_input: &Self::Input, _part: u32) -> Option<String> {
+        None
+    }
+}
+
+pub fn run_part_1<S: Solution>(input: &str) -> Result<String, ParseError> {
+    S::parse(input).map(|i| S::part_1(&i).to_string())
+}
+
+pub fn run_part_2<S: Solution>(input: &str) -> Result<String, ParseError> {
+    S::parse(input).map(|i| S::part_2(&i).to_string())
+}
+
+pub fn run_render<S: Solution>(input: &str, part: u32) -> Result<Option<String>, ParseError> {
+    S::parse(input).map(|i| S::render(&i, part))
+}
@@ -0,0 +1,82 @@
+use crate::error::{source_lines, ParseError};
+use crate::solution::Solution;
+pub fn sonar_sweep(input: &[u32], sweep_range: usize) -> i32 {
+    let mut depth_increases = 0;
+
+    let mut prev_depth = input[0];
+
+    for i in 1..input.len() {
+        let mut accum_depth = input[i];
+        for j in 1..sweep_range {
+            if i + j < input.len() {
+                accum_depth += input[i + j];
+            }
+        }
+
+        if accum_depth > prev_depth {
+            depth_increases += 1;
+        }
+        prev_depth = accum_depth;
+    }
+
+    depth_increases
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<u32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let depths = source_lines(input)
+            .map(|line| line.parse::<u32>(line.text, "depth"))
+            .collect::<Result<Vec<_>, _>>()?;
+        if depths.is_empty() {
+            return Err(ParseError::missing(1, "depth"));
+        }
+        Ok(depths)
+    }
+
+    fn part_1(depths: &Self::Input) -> Self::Answer1 {
+        sonar_sweep(depths, 1)
+    }
+
+    fn part_2(depths: &Self::Input) -> Self::Answer2 {
+        sonar_sweep(depths, 3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
+
+    #[test]
+    fn day_1() {
+        use super::sonar_sweep;
+        let file = &load(2021, 1, Variant::Real).unwrap();
+
+        let int_vec: Vec<u32> = file.lines().map(|x| x.parse::<u32>().unwrap()).collect();
+        let result = sonar_sweep(&int_vec, 1);
+        assert_answer(2021, 1, Variant::Real, 1, result);
+
+        let result_extra = sonar_sweep(&int_vec, 3);
+        assert_answer(2021, 1, Variant::Real, 2, result_extra);
+    }
+
+    #[test]
+    fn malformed() {
+        use super::{Day1, ParseError, Solution};
+
+        assert_eq!(
+            ParseError::missing(1, "depth"),
+            Day1::parse("").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::unexpected(2, 1, "x", "depth"),
+            Day1::parse("199\nx").unwrap_err()
+        );
+    }
+}
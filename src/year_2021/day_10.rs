@@ -1,3 +1,5 @@
+use crate::error::{source_lines, ParseError};
+use crate::solution::Solution;
 use std::collections::HashMap;
 
 enum SyntaxResult {
@@ -113,7 +115,7 @@ fn score_incomplete_results(results: &[SyntaxResult]) -> u64 {
 }
 
 fn part_1(lines: &str) -> u32 {
-    let results: Vec<SyntaxResult> = lines.lines().map(|l| parse_line(l)).collect();
+    let results: Vec<SyntaxResult> = lines.lines().map(parse_line).collect();
 
     score_error_results(&results)
 }
@@ -121,27 +123,69 @@ fn part_1(lines: &str) -> u32 {
 fn part_2(lines: &str) -> u64 {
     let results: Vec<SyntaxResult> = lines
         .lines()
-        .map(|l| parse_line(l))
+        .map(parse_line)
         .filter(|r| matches!(r, SyntaxResult::Incomplete(_)))
         .collect();
 
     score_incomplete_results(&results)
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = String;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        for line in source_lines(input) {
+            if let Some((i, c)) = line
+                .text
+                .char_indices()
+                .find(|&(_, c)| !"{}[]<>()".contains(c))
+            {
+                return Err(line.error(&line.text[i..i + c.len_utf8()], "bracket"));
+            }
+        }
+
+        Ok(input.to_string())
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
 
     #[test]
     fn example() {
-        let lines = include_str!("../input/day_10_example.txt");
-        assert_eq!(26397, super::part_1(lines));
-        assert_eq!(288957, super::part_2(lines));
+        let lines = &load(2021, 10, Variant::Example(1)).unwrap();
+        assert_answer(2021, 10, Variant::Example(1), 1, super::part_1(lines));
+        assert_answer(2021, 10, Variant::Example(1), 2, super::part_2(lines));
     }
 
     #[test]
     fn actual() {
-        let lines = include_str!("../input/day_10.txt");
-        assert_eq!(316851, super::part_1(lines));
-        assert_eq!(2182912364, super::part_2(lines));
+        let lines = &load(2021, 10, Variant::Real).unwrap();
+        assert_answer(2021, 10, Variant::Real, 1, super::part_1(lines));
+        assert_answer(2021, 10, Variant::Real, 2, super::part_2(lines));
+    }
+
+    #[test]
+    fn malformed() {
+        use super::{Day10, ParseError, Solution};
+
+        assert_eq!(
+            ParseError::unexpected(2, 2, "é", "bracket"),
+            Day10::parse("[]\n(é)").unwrap_err()
+        );
     }
 }
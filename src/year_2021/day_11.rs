@@ -0,0 +1,130 @@
+use crate::common::Grid;
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+fn step(octopuses: &mut Grid<u32>) -> u32 {
+    let mut flashing_octopuses = vec![];
+    for (i, o) in &mut octopuses.cells.iter_mut().enumerate() {
+        *o += 1;
+
+        if *o > 9 {
+            flashing_octopuses.push(i);
+        }
+    }
+
+    let mut flash_count = 0;
+    let mut flashed = vec![];
+    while !flashing_octopuses.is_empty() {
+        flash_count += 1;
+        let octopus = flashing_octopuses.pop().unwrap();
+        flashing_octopuses.extend(flash(octopuses, octopus));
+        flashed.push(octopus);
+    }
+
+    for &i in flashed.iter() {
+        octopuses.cells[i] = 0;
+    }
+
+    flash_count
+}
+
+fn flash(octopuses: &mut Grid<u32>, octopus: usize) -> Vec<usize> {
+    let mut flashing_neighbors = vec![];
+    for n in octopuses.neighbors(octopus, true).iter() {
+        octopuses.cells[*n] += 1;
+        if octopuses.cells[*n] == 10 {
+            flashing_neighbors.push(*n);
+        }
+    }
+
+    flashing_neighbors
+}
+
+fn part_1(octopuses: &mut Grid<u32>, flash_count: u32) -> u32 {
+    let mut flashes = 0;
+    for _ in 0..flash_count {
+        flashes += step(octopuses);
+    }
+
+    flashes
+}
+
+fn part_2(octopuses: &mut Grid<u32>) -> u32 {
+    let mut stepcount = 0;
+    loop {
+        let flashed = step(octopuses);
+        stepcount += 1;
+        if flashed == octopuses.cells.len() as u32 {
+            return stepcount;
+        }
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Grid<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse::<Grid<u32>>()
+    }
+
+    fn part_1(octopuses: &Self::Input) -> Self::Answer1 {
+        part_1(&mut octopuses.clone(), 100)
+    }
+
+    fn part_2(octopuses: &Self::Input) -> Self::Answer2 {
+        part_2(&mut octopuses.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
+
+    #[test]
+    fn example() {
+        let mut octopuses = load(2021, 11, Variant::Example(1))
+            .unwrap()
+            .parse::<super::Grid<u32>>()
+            .unwrap();
+
+        let flashes = super::part_1(&mut octopuses, 100);
+        assert_answer(2021, 11, Variant::Example(1), 1, flashes);
+    }
+
+    #[test]
+    fn example_2() {
+        let mut octopuses = load(2021, 11, Variant::Example(1))
+            .unwrap()
+            .parse::<super::Grid<u32>>()
+            .unwrap();
+
+        let step = super::part_2(&mut octopuses);
+        assert_answer(2021, 11, Variant::Example(1), 2, step);
+    }
+
+    #[test]
+    fn actual() {
+        let mut octopuses = load(2021, 11, Variant::Real)
+            .unwrap()
+            .parse::<super::Grid<u32>>()
+            .unwrap();
+
+        let flashes = super::part_1(&mut octopuses, 100);
+        assert_answer(2021, 11, Variant::Real, 1, flashes);
+    }
+
+    #[test]
+    fn actual_2() {
+        let mut octopuses = load(2021, 11, Variant::Real)
+            .unwrap()
+            .parse::<super::Grid<u32>>()
+            .unwrap();
+
+        assert_answer(2021, 11, Variant::Real, 2, super::part_2(&mut octopuses));
+    }
+}
@@ -0,0 +1,410 @@
+use crate::error::{source_lines, ParseError, SourceLine};
+use crate::solution::Solution;
+use std::collections::HashMap;
+
+/// Small caves are tracked as bits of a `u128`.
+const MAX_SMALL_CAVES: u32 = 128;
+
+/// Hands out dense ids for names, and the names back for ids.
+#[derive(Default)]
+struct Interner {
+    ids: HashMap<String, usize>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    fn get(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    fn intern(&mut self, name: &str) -> usize {
+        if let Some(id) = self.get(name) {
+            return id;
+        }
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), self.names.len() - 1);
+        self.names.len() - 1
+    }
+
+    fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CaveType {
+    Start,
+    End,
+    Big,
+    /// The bit of the cave in a visited set.
+    Small(u128),
+}
+
+struct Cave {
+    cave_type: CaveType,
+    neighbors: Vec<usize>,
+}
+
+/// Caves indexed by the ids of their names.
+#[derive(Default)]
+pub struct Graph {
+    names: Interner,
+    caves: Vec<Cave>,
+    small_caves: u32,
+}
+
+fn is_small(name: &str) -> bool {
+    !matches!(name, "start" | "end") && name != name.to_uppercase()
+}
+
+impl Graph {
+    /// Id of the cave `name`, a slice of `line`, adding the cave if it is new.
+    fn cave(&mut self, line: &SourceLine, name: &str) -> Result<usize, ParseError> {
+        if is_small(name) && self.names.get(name).is_none() && self.small_caves == MAX_SMALL_CAVES {
+            return Err(line.error(name, format!("at most {} small caves", MAX_SMALL_CAVES)));
+        }
+        let id = self.names.intern(name);
+        if id == self.caves.len() {
+            let cave_type = match name {
+                "start" => CaveType::Start,
+                "end" => CaveType::End,
+                _ if is_small(name) => {
+                    self.small_caves += 1;
+                    CaveType::Small(1 << (self.small_caves - 1))
+                }
+                _ => CaveType::Big,
+            };
+            self.caves.push(Cave {
+                cave_type,
+                neighbors: Vec::new(),
+            });
+        }
+        Ok(id)
+    }
+
+    fn add_connection(&mut self, line: &SourceLine, a: &str, b: &str) -> Result<(), ParseError> {
+        let (a, b) = (self.cave(line, a)?, self.cave(line, b)?);
+        if !self.caves[a].neighbors.contains(&b) {
+            self.caves[a].neighbors.push(b);
+            self.caves[b].neighbors.push(a);
+        }
+        Ok(())
+    }
+
+    /// Every path from `start` to `end` that visits small caves at most once,
+    /// or, with `revisit`, a single small cave twice.
+    pub fn paths(&self, revisit: bool) -> Paths<'_> {
+        let mut paths = Paths {
+            graph: self,
+            stack: Vec::new(),
+            visited: 0,
+            revisit,
+            revisited_at: None,
+        };
+        if let Some(start) = self.names.get("start") {
+            paths.enter(start);
+        }
+        paths
+    }
+
+    /// Number of paths from `cave` to `end` when the small caves in `visited`
+    /// were already entered, remembering the count for every such state.
+    fn count_from(
+        &self,
+        cave: usize,
+        visited: u128,
+        revisit: bool,
+        memo: &mut HashMap<(usize, u128, bool), u64>,
+    ) -> u64 {
+        if let Some(&count) = memo.get(&(cave, visited, revisit)) {
+            return count;
+        }
+
+        let mut count = 0;
+        for &next in &self.caves[cave].neighbors {
+            count += match self.caves[next].cave_type {
+                CaveType::Start => 0,
+                CaveType::End => 1,
+                CaveType::Big => self.count_from(next, visited, revisit, memo),
+                CaveType::Small(bit) if visited & bit == 0 => {
+                    self.count_from(next, visited | bit, revisit, memo)
+                }
+                CaveType::Small(_) if revisit => self.count_from(next, visited, false, memo),
+                CaveType::Small(_) => 0,
+            };
+        }
+
+        memo.insert((cave, visited, revisit), count);
+        count
+    }
+
+    /// Number of paths `paths` would yield, without walking each of them.
+    pub fn paths_through(&self, revisit: bool) -> u64 {
+        match self.names.get("start") {
+            Some(start) => self.count_from(start, 0, revisit, &mut HashMap::new()),
+            None => 0,
+        }
+    }
+}
+
+/// Iterator over the paths through a `Graph`, each as the names of the caves
+/// along it, found depth first.
+pub struct Paths<'a> {
+    graph: &'a Graph,
+    /// The caves on the current path, each with the neighbours still to try.
+    stack: Vec<(usize, Vec<usize>)>,
+    /// The small caves on the current path.
+    visited: u128,
+    revisit: bool,
+    /// Length of the path when it entered a small cave for the second time.
+    revisited_at: Option<usize>,
+}
+
+impl<'a> Paths<'a> {
+    fn enter(&mut self, id: usize) {
+        let graph = self.graph;
+        if let CaveType::Small(bit) = graph.caves[id].cave_type {
+            self.visited |= bit;
+        }
+        // neighbours are tried in alphabetical order, popped from the back
+        let mut pending = graph.caves[id].neighbors.clone();
+        pending.sort_by(|&a, &b| graph.names.name(b).cmp(graph.names.name(a)));
+        self.stack.push((id, pending));
+    }
+
+    fn leave(&mut self) {
+        if self.revisited_at == Some(self.stack.len()) {
+            // the cave is still on the path from its first visit
+            self.revisited_at = None;
+        } else if let Some(CaveType::Small(bit)) = self
+            .stack
+            .last()
+            .map(|&(id, _)| self.graph.caves[id].cave_type)
+        {
+            self.visited &= !bit;
+        }
+        self.stack.pop();
+    }
+
+    fn path_to(&self, end: usize) -> Vec<&'a str> {
+        let names = &self.graph.names;
+        self.stack
+            .iter()
+            .map(|&(id, _)| id)
+            .chain(std::iter::once(end))
+            .map(|id| names.name(id))
+            .collect()
+    }
+}
+
+impl<'a> Iterator for Paths<'a> {
+    type Item = Vec<&'a str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let next = match self.stack.last_mut()?.1.pop() {
+                Some(next) => next,
+                None => {
+                    self.leave();
+                    continue;
+                }
+            };
+
+            match self.graph.caves[next].cave_type {
+                CaveType::Start => {}
+                CaveType::End => return Some(self.path_to(next)),
+                CaveType::Big => self.enter(next),
+                CaveType::Small(bit) if self.visited & bit == 0 => self.enter(next),
+                CaveType::Small(_) if self.revisit && self.revisited_at.is_none() => {
+                    self.enter(next);
+                    self.revisited_at = Some(self.stack.len());
+                }
+                CaveType::Small(_) => {}
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for Graph {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut graph = Graph::default();
+
+        for line in source_lines(s) {
+            let (a, b) = line.split_once("-")?;
+            for cave in [a, b] {
+                if cave.is_empty() || !cave.chars().all(|c| c.is_ascii_alphabetic()) {
+                    return Err(line.error(cave, "cave name"));
+                }
+            }
+            // two big caves could be walked back and forth forever
+            let big = |cave: &str| cave != "start" && cave != "end" && !is_small(cave);
+            if big(a) && big(b) {
+                return Err(line.error(line.text, "connection with a small cave"));
+            }
+            graph.add_connection(&line, a, b)?;
+        }
+
+        if graph.names.get("start").is_none() {
+            return Err(ParseError::missing(
+                s.lines().count() + 1,
+                "connection to 'start'",
+            ));
+        }
+        Ok(graph)
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Graph;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse::<Graph>()
+    }
+
+    fn part_1(graph: &Self::Input) -> Self::Answer1 {
+        graph.paths_through(false)
+    }
+
+    fn part_2(graph: &Self::Input) -> Self::Answer2 {
+        graph.paths_through(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
+    use std::collections::HashSet;
+
+    #[test]
+    fn example() {
+        let graph = load(2021, 12, Variant::Example(1))
+            .unwrap()
+            .parse::<Graph>()
+            .unwrap();
+
+        let paths = graph.paths(false).map(|p| p.join(",")).collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "start,A,b,A,c,A,end",
+                "start,A,b,A,end",
+                "start,A,b,end",
+                "start,A,c,A,b,A,end",
+                "start,A,c,A,b,end",
+                "start,A,c,A,end",
+                "start,A,end",
+                "start,b,A,c,A,end",
+                "start,b,A,end",
+                "start,b,end",
+            ],
+            paths
+        );
+
+        assert_answer(2021, 12, Variant::Example(1), 1, graph.paths_through(false));
+        assert_answer(2021, 12, Variant::Example(1), 2, graph.paths_through(true));
+    }
+
+    #[test]
+    fn revisits() {
+        let graph = load(2021, 12, Variant::Example(1))
+            .unwrap()
+            .parse::<Graph>()
+            .unwrap();
+
+        let paths = graph.paths(true).collect::<Vec<_>>();
+        assert!(paths.contains(&vec!["start", "A", "b", "A", "b", "A", "c", "A", "end"]));
+        assert!(paths.contains(&vec!["start", "b", "d", "b", "A", "c", "A", "end"]));
+        for path in paths {
+            let revisited = path
+                .iter()
+                .filter(|c| c.chars().all(|c| c.is_ascii_lowercase()))
+                .filter(|&c| path.iter().filter(|&p| p == c).count() > 1)
+                .collect::<HashSet<_>>();
+            assert!(
+                revisited.len() <= 1,
+                "{:?} revisits more than one cave",
+                path
+            );
+            assert!(!revisited.contains(&"start"));
+        }
+
+        let dead_end = "start-a\nb-end".parse::<Graph>().unwrap();
+        assert_eq!(0, dead_end.paths(true).count());
+        assert_eq!(0, dead_end.paths_through(true));
+    }
+
+    #[test]
+    fn counts_match_paths() {
+        for variant in [Variant::Example(1), Variant::Real] {
+            let graph = load(2021, 12, variant).unwrap().parse::<Graph>().unwrap();
+            for revisit in [false, true] {
+                let count = graph.paths(revisit).count() as u64;
+                assert_eq!(count, graph.paths_through(revisit));
+            }
+        }
+    }
+
+    #[test]
+    fn large_graph() {
+        // one big cave leading to `end` and to 12 small dead ends, so a path
+        // visits any ordered selection of the small caves
+        let mut input = String::from("start-A\nA-end");
+        for c in 'a'..='l' {
+            input += &format!("\nA-{}", c);
+        }
+        let graph = input.parse::<Graph>().unwrap();
+        let arrangements = (0..=12).map(|k| (12 - k + 1..=12).product::<u64>());
+        assert_eq!(arrangements.sum::<u64>(), graph.paths_through(false));
+    }
+
+    #[test]
+    fn too_many_small_caves() {
+        let mut input = String::from("start-end");
+        for i in 0..=MAX_SMALL_CAVES {
+            let name = format!("{:b}", i).replace('0', "a").replace('1', "b");
+            input += &format!("\nend-{}", name);
+        }
+        assert_eq!(
+            ParseError::unexpected(130, 5, "baaaaaaa", "at most 128 small caves"),
+            input.parse::<Graph>().err().unwrap()
+        );
+
+        // the last line brings two new small caves when only one more fits
+        let mut input = String::from("start-end");
+        for i in 1..MAX_SMALL_CAVES {
+            let name = format!("{:b}", i).replace('0', "a").replace('1', "b");
+            input += &format!("\nend-{}", name);
+        }
+        input += "\nxy-xz";
+        assert_eq!(
+            ParseError::unexpected(129, 4, "xz", "at most 128 small caves"),
+            input.parse::<Graph>().err().unwrap()
+        );
+    }
+
+    #[test]
+    fn big_caves_connected() {
+        assert_eq!(
+            ParseError::unexpected(2, 1, "A-B", "connection with a small cave"),
+            "start-A\nA-B\nB-end".parse::<Graph>().err().unwrap()
+        );
+    }
+
+    #[test]
+    fn actual() {
+        let graph = load(2021, 12, Variant::Real)
+            .unwrap()
+            .parse::<Graph>()
+            .unwrap();
+
+        assert_answer(2021, 12, Variant::Real, 1, graph.paths_through(false));
+        assert_answer(2021, 12, Variant::Real, 2, graph.paths_through(true));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::error::{source_lines, ParseError};
+use crate::solution::Solution;
 use std::collections::HashMap;
 
 #[derive(Clone, Copy)]
@@ -12,38 +14,51 @@ struct Grid {
     width: usize,
     height: usize,
 }
-struct Data {
+pub struct Data {
     grid: Grid,
     folds: Vec<Fold>,
 }
 
 impl std::str::FromStr for Data {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let (mut x_max, mut y_max) = (0, 0);
         let mut points: Vec<(i32, i32)> = Vec::with_capacity(1000);
         let mut folds: Vec<Fold> = Vec::with_capacity(12);
-        for l in s.lines() {
-            if l.contains(',') {
-                let (x, y) = l.split_once(',').ok_or("")?;
-                let point = (x.parse::<i32>().unwrap(), y.parse::<i32>().unwrap());
+        for l in source_lines(s) {
+            if l.text.contains(',') {
+                let (x, y) = l.split_once(",")?;
+                let point = (
+                    l.parse::<i32>(x, "x coordinate")?,
+                    l.parse::<i32>(y, "y coordinate")?,
+                );
                 x_max = x_max.max(point.0 + 1);
                 y_max = y_max.max(point.1 + 1);
                 points.push(point);
-            } else if l.contains("fold") {
-                let fold = l.trim_start_matches("fold along ");
-                let (axis_str, coord_str) = fold.split_once('=').ok_or("")?;
-                let coord = coord_str.parse::<usize>().unwrap();
-                let fold = if axis_str.starts_with('x') {
-                    Fold::X(coord)
-                } else {
-                    Fold::Y(coord)
+            } else if l.text.contains("fold") {
+                let fold = l.text.trim_start_matches("fold along ");
+                let (axis_str, coord_str) = fold
+                    .split_once('=')
+                    .ok_or_else(|| l.error(fold, "'x=' or 'y='"))?;
+                let coord = l.parse::<usize>(coord_str, "fold coordinate")?;
+                let fold = match axis_str {
+                    "x" => Fold::X(coord),
+                    "y" => Fold::Y(coord),
+                    _ => return Err(l.error(axis_str, "'x' or 'y'")),
                 };
 
                 folds.push(fold);
+            } else if !l.text.trim().is_empty() {
+                return Err(l.error(l.text, "'x,y' or 'fold along'"));
             }
         }
+        if folds.is_empty() {
+            return Err(ParseError::missing(
+                s.lines().count() + 1,
+                "fold instruction",
+            ));
+        }
         let mut data = Data {
             grid: Grid {
                 width: x_max as usize,
@@ -104,28 +119,62 @@ fn fold(heightmap: &Grid, fold: Fold) -> Grid {
     }
 }
 
-fn part_2(data: &Data) {
+fn dot_count(grid: &Grid) -> usize {
+    grid.points.values().filter(|&&count| count > 0).count()
+}
+
+fn render(grid: &Grid) -> String {
+    let mut output = String::with_capacity((grid.width + 1) * grid.height);
+    for y in 0..grid.height {
+        for x in 0..grid.width {
+            let dot = grid.points.contains_key(&(x as i32, y as i32));
+            output.push(if dot { '.' } else { ' ' });
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+fn part_2(data: &Data) -> String {
     let mut grid = data.grid.clone();
     for &f in data.folds.iter() {
         let folded = fold(&grid, f);
         grid = folded;
     }
 
-    for y in 0..grid.height {
-        for x in 0..grid.width {
-            let dot = grid.points.contains_key(&(x as i32, y as i32));
-            print!("{}", if dot { '.' } else { ' ' });
-        }
-        println!();
+    render(&grid)
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Data;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse::<Data>()
+    }
+
+    fn part_1(data: &Self::Input) -> Self::Answer1 {
+        dot_count(&fold(&data.grid, data.folds[0]))
+    }
+
+    fn part_2(data: &Self::Input) -> Self::Answer2 {
+        part_2(data)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
 
     #[test]
     fn example() {
-        let data = include_str!("../input/day_13_example.txt")
+        let data = load(2021, 13, Variant::Example(1))
+            .unwrap()
             .parse::<super::Data>()
             .unwrap();
 
@@ -146,7 +195,8 @@ mod tests {
 
     #[test]
     fn actual() {
-        let data = include_str!("../input/day_13.txt")
+        let data = load(2021, 13, Variant::Real)
+            .unwrap()
             .parse::<super::Data>()
             .unwrap();
 
@@ -155,8 +205,8 @@ mod tests {
             .points
             .iter()
             .fold(0, |acc, (&_, &count)| acc + if count > 0 { 1 } else { 0 });
-        assert_eq!(837, dot_count);
+        assert_answer(2021, 13, Variant::Real, 1, dot_count);
 
-        super::part_2(&data);
+        assert_answer(2021, 13, Variant::Real, 2, super::part_2(&data));
     }
 }
@@ -1,21 +1,34 @@
+use crate::error::{source_lines, ParseError};
+use crate::solution::Solution;
 use std::collections::HashMap;
 
-struct Data {
+pub struct Data {
     template: String,
     pair_insertions: HashMap<(char, char), char>,
 }
 
 impl std::str::FromStr for Data {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let template = s.lines().next().ok_or("")?;
+        let template = match s.lines().next() {
+            Some(template) if !template.is_empty() => template,
+            _ => return Err(ParseError::missing(1, "polymer template")),
+        };
         let mut pair_insertions = HashMap::default();
 
-        for pair_insertion in s.lines().skip(2) {
-            let a = pair_insertion.chars().next().ok_or("")?;
-            let b = pair_insertion.chars().nth(1).ok_or("")?;
-            let c = pair_insertion.chars().nth(6).ok_or("")?;
+        for line in source_lines(s).skip(2) {
+            let (pair, element) = line.split_once(" -> ")?;
+            let mut pair_chars = pair.chars();
+            let (a, b) = match (pair_chars.next(), pair_chars.next(), pair_chars.next()) {
+                (Some(a), Some(b), None) => (a, b),
+                _ => return Err(line.error(pair, "pair of elements")),
+            };
+            let mut element_chars = element.chars();
+            let c = match (element_chars.next(), element_chars.next()) {
+                (Some(c), None) => c,
+                _ => return Err(line.error(element, "single element")),
+            };
             pair_insertions.insert((a, b), c);
         }
 
@@ -88,23 +101,47 @@ fn solution(data: &Data, step_count: u32) -> i64 {
     max - min
 }
 
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Data;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse::<Data>()
+    }
+
+    fn part_1(data: &Self::Input) -> Self::Answer1 {
+        solution(data, 10)
+    }
+
+    fn part_2(data: &Self::Input) -> Self::Answer2 {
+        solution(data, 40)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
 
     #[test]
     fn example() {
-        let data = include_str!("../input/day_14_example.txt")
+        let data = load(2021, 14, Variant::Example(1))
+            .unwrap()
             .parse::<super::Data>()
             .unwrap();
-        assert_eq!(1588, super::solution(&data, 10));
+        assert_answer(2021, 14, Variant::Example(1), 1, super::solution(&data, 10));
     }
 
     #[test]
     fn actual() {
-        let data = include_str!("../input/day_14.txt")
+        let data = load(2021, 14, Variant::Real)
+            .unwrap()
             .parse::<super::Data>()
             .unwrap();
-        assert_eq!(3058, super::solution(&data, 10));
-        assert_eq!(3447389044530, super::solution(&data, 40));
+        assert_answer(2021, 14, Variant::Real, 1, super::solution(&data, 10));
+        assert_answer(2021, 14, Variant::Real, 2, super::solution(&data, 40));
     }
 }
@@ -0,0 +1,280 @@
+use crate::common::search;
+use crate::common::Grid;
+use crate::error::ParseError;
+use crate::solution::Solution;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+/// The cave map repeated `repeats` times in both directions, where each
+/// repetition to the right or down raises every risk level by one, wrapping
+/// from 9 back to 1. Risk levels are worked out when they are read, so the
+/// view itself takes no more memory than the tile however far it repeats.
+pub struct TiledMap<'a> {
+    tile: &'a Grid<u32>,
+    repeats: usize,
+}
+
+impl<'a> TiledMap<'a> {
+    pub fn new(tile: &'a Grid<u32>, repeats: usize) -> TiledMap<'a> {
+        TiledMap { tile, repeats }
+    }
+
+    pub fn width(&self) -> usize {
+        self.tile.width * self.repeats
+    }
+
+    pub fn height(&self) -> usize {
+        self.tile.height * self.repeats
+    }
+
+    pub fn len(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn position(&self, index: usize) -> (usize, usize) {
+        (index % self.width(), index / self.width())
+    }
+
+    /// Risk level of the cell at `index`, given tile risks from 1 to 9.
+    pub fn risk(&self, index: usize) -> u32 {
+        let (x, y) = self.position(index);
+        let (x_mul, y_mul) = (x / self.tile.width, y / self.tile.height);
+        let h = self.tile.cells[(y % self.tile.height) * self.tile.width + x % self.tile.width];
+        ((h as usize + x_mul + y_mul - 1) % 9 + 1) as u32
+    }
+
+    /// Indices of the cells up, down, left and right of `index`.
+    pub fn neighbors(&self, index: usize) -> impl Iterator<Item = usize> {
+        let (width, height) = (self.width(), self.height());
+        let (x, y) = self.position(index);
+        let left = (x > 0).then(|| index - 1);
+        let right = (x + 1 < width).then(|| index + 1);
+        let up = (y > 0).then(|| index - width);
+        let down = (y + 1 < height).then(|| index + width);
+        IntoIterator::into_iter([left, right, up, down]).flatten()
+    }
+}
+
+/// The lowest total risk from `start` to `goal`, not counting the start
+/// cell, by Dijkstra's algorithm over the cell indices. When `parents` is
+/// given, each cell reached is set to the index it was entered from.
+fn search(
+    start: usize,
+    goal: usize,
+    map: &TiledMap,
+    mut parents: Option<&mut Vec<usize>>,
+) -> Option<u32> {
+    let mut risks = vec![u32::MAX; map.len()];
+    risks[start] = 0;
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse((0, start)));
+
+    while let Some(Reverse((risk, index))) = heap.pop() {
+        if index == goal {
+            return Some(risk);
+        }
+        if risk > risks[index] {
+            continue;
+        }
+        for next in map.neighbors(index) {
+            let next_risk = risk + map.risk(next);
+            if next_risk < risks[next] {
+                risks[next] = next_risk;
+                if let Some(parents) = parents.as_deref_mut() {
+                    parents[next] = index;
+                }
+                heap.push(Reverse((next_risk, next)));
+            }
+        }
+    }
+
+    None
+}
+
+/// The path from `start` to `goal` with the lowest total risk, as the cell
+/// indices it passes through. The risk of the start cell is not counted.
+pub fn safest_path(start: usize, goal: usize, map: &TiledMap) -> Option<search::Path<usize>> {
+    let mut parents = vec![start; map.len()];
+    let cost = search(start, goal, map, Some(&mut parents))?;
+    let mut states = vec![goal];
+    while states[states.len() - 1] != start {
+        states.push(parents[states[states.len() - 1]]);
+    }
+    states.reverse();
+
+    Some(search::Path {
+        cost: cost as usize,
+        states,
+    })
+}
+
+fn lowest_risk(map: &TiledMap) -> usize {
+    search(0, map.len() - 1, map, None).unwrap() as usize
+}
+
+/// How many times the map is repeated in each direction for `part`.
+fn repeats(part: u32) -> usize {
+    if part == 2 {
+        5
+    } else {
+        1
+    }
+}
+
+/// Draws the risk levels along `path`, with every other cell as '.' or, with
+/// `ansi`, every risk level with the path in bold.
+pub fn render(map: &TiledMap, path: &[usize], ansi: bool) -> String {
+    let on_path = path.iter().copied().collect::<HashSet<_>>();
+    let mut output = String::with_capacity((map.width() + 1) * map.height());
+    for index in 0..map.len() {
+        let digit = std::char::from_digit(map.risk(index), 10).unwrap_or('?');
+        match (on_path.contains(&index), ansi) {
+            (true, true) => output += &format!("\x1b[1;97m{}\x1b[0m", digit),
+            (false, true) | (true, false) => output.push(digit),
+            (false, false) => output.push('.'),
+        }
+        if (index + 1) % map.width() == 0 {
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Grid<u32>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(input, "risk level 1 to 9", |c| {
+            c.to_digit(10).filter(|&risk| risk > 0)
+        })
+    }
+
+    fn part_1(map: &Self::Input) -> Self::Answer1 {
+        lowest_risk(&TiledMap::new(map, repeats(1)))
+    }
+
+    fn part_2(map: &Self::Input) -> Self::Answer2 {
+        lowest_risk(&TiledMap::new(map, repeats(2)))
+    }
+
+    fn render(map: &Self::Input, part: u32) -> Option<String> {
+        let map = TiledMap::new(map, repeats(part));
+        let path = safest_path(0, map.len() - 1, &map)?;
+        Some(render(&map, &path.states, false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
+
+    /// The safest path through the whole map, drawn over it.
+    fn safest_route(map: &TiledMap) -> (usize, String) {
+        let path = safest_path(0, map.len() - 1, map).unwrap();
+        assert_eq!(0, path.states[0]);
+        let entered = path.states[1..].iter().map(|&i| map.risk(i) as usize);
+        assert_eq!(path.cost, entered.sum::<usize>());
+        (path.cost, render(map, &path.states, false))
+    }
+
+    #[test]
+    fn example() {
+        let data = load(2021, 15, Variant::Example(1))
+            .unwrap()
+            .parse::<Grid<u32>>()
+            .unwrap();
+        let (risk, route) = safest_route(&TiledMap::new(&data, 1));
+        assert_answer(2021, 15, Variant::Example(1), 1, risk);
+        assert_eq!(
+            "1.........\n\
+             1.........\n\
+             2136511...\n\
+             ......15..\n\
+             .......11.\n\
+             ........3.\n\
+             ........2.\n\
+             ........3.\n\
+             ........21\n\
+             .........1\n",
+            route
+        );
+
+        let (risk, route) = safest_route(&TiledMap::new(&data, 5));
+        assert_answer(2021, 15, Variant::Example(1), 2, risk);
+        assert_eq!(50, route.lines().count());
+    }
+
+    #[test]
+    fn tiles() {
+        let data = load(2021, 15, Variant::Example(1))
+            .unwrap()
+            .parse::<Grid<u32>>()
+            .unwrap();
+        let map = TiledMap::new(&data, 5);
+        let first_row = (0..map.width())
+            .map(|x| std::char::from_digit(map.risk(x), 10).unwrap())
+            .collect::<String>();
+        assert_eq!(
+            "11637517422274862853338597396444961841755517295286",
+            first_row
+        );
+        assert_eq!(9, map.risk(map.len() - 1));
+
+        // far past the repeats anyone would materialize, risks keep wrapping
+        let huge = TiledMap::new(&data, 1_000_000);
+        let last_tile = (huge.width() - 10) * (huge.width() + 1);
+        // raised 2 * 999_999 times, a multiple of 9, so back to its own risk
+        assert_eq!(data.cells[0], huge.risk(last_tile));
+        assert_eq!(2, huge.neighbors(0).count());
+        assert_eq!(4, huge.neighbors(last_tile).count());
+
+        let (_, route) = safest_route(&TiledMap::new(&data, 50));
+        assert_eq!(500, route.lines().count());
+    }
+
+    #[test]
+    fn ansi() {
+        let map = "19\n11".parse::<Grid<u32>>().unwrap();
+        let map = TiledMap::new(&map, 1);
+        let path = safest_path(0, 3, &map).unwrap();
+        assert_eq!(vec![0, 2, 3], path.states);
+        assert_eq!(
+            "\x1b[1;97m1\x1b[0m9\n\x1b[1;97m1\x1b[0m\x1b[1;97m1\x1b[0m\n",
+            render(&map, &path.states, true)
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            ParseError::unexpected(2, 2, "0", "risk level 1 to 9"),
+            Day15::parse("19\n10").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn actual() {
+        let data = load(2021, 15, Variant::Real)
+            .unwrap()
+            .parse::<Grid<u32>>()
+            .unwrap();
+        let (risk, route) = safest_route(&TiledMap::new(&data, 1));
+        assert_answer(2021, 15, Variant::Real, 1, risk);
+        assert_eq!(Some(route), Day15::render(&data, 1));
+
+        let (risk, route) = safest_route(&TiledMap::new(&data, 5));
+        assert_answer(2021, 15, Variant::Real, 2, risk);
+        assert_eq!(Some(route), Day15::render(&data, 2));
+    }
+}
@@ -0,0 +1,296 @@
+use crate::error::{ParseError, SourceLine};
+use crate::solution::Solution;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PacketKind {
+    Literal(u64),
+    Operator(Operator, Vec<Packet>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Packet {
+    version: u8,
+    kind: PacketKind,
+}
+
+impl Packet {
+    pub fn version_sum(&self) -> u64 {
+        let nested = match &self.kind {
+            PacketKind::Literal(_) => 0,
+            PacketKind::Operator(_, packets) => packets.iter().map(Packet::version_sum).sum(),
+        };
+        self.version as u64 + nested
+    }
+
+    pub fn evaluate(&self) -> u64 {
+        let (operator, packets) = match &self.kind {
+            PacketKind::Literal(value) => return *value,
+            PacketKind::Operator(operator, packets) => (operator, packets),
+        };
+
+        let mut values = packets.iter().map(Packet::evaluate);
+        match operator {
+            Operator::Sum => values.sum(),
+            Operator::Product => values.product(),
+            Operator::Minimum => values.min().unwrap_or_default(),
+            Operator::Maximum => values.max().unwrap_or_default(),
+            comparison => {
+                let (a, b) = (values.next().unwrap(), values.next().unwrap());
+                let result = match comparison {
+                    Operator::GreaterThan => a > b,
+                    Operator::LessThan => a < b,
+                    _ => a == b,
+                };
+                result as u64
+            }
+        }
+    }
+}
+
+/// Reads a hexadecimal transmission one bit at a time, most significant bit first.
+struct BitReader<'a> {
+    line: SourceLine<'a>,
+    nibbles: Vec<u8>,
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(line: SourceLine<'a>) -> Result<BitReader<'a>, ParseError> {
+        let nibbles = line
+            .text
+            .char_indices()
+            .map(|(i, c)| {
+                c.to_digit(16)
+                    .map(|d| d as u8)
+                    .ok_or_else(|| line.error(&line.text[i..i + c.len_utf8()], "hexadecimal digit"))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(BitReader {
+            line,
+            nibbles,
+            position: 0,
+        })
+    }
+
+    fn read(&mut self, bit_count: usize, expected: &str) -> Result<u64, ParseError> {
+        if self.position + bit_count > self.nibbles.len() * 4 {
+            return Err(ParseError::missing(self.line.number, expected));
+        }
+
+        let mut value = 0;
+        for _ in 0..bit_count {
+            let nibble = self.nibbles[self.position / 4];
+            let bit = (nibble >> (3 - self.position % 4)) & 1;
+            value = (value << 1) | bit as u64;
+            self.position += 1;
+        }
+        Ok(value)
+    }
+
+    /// The hex digit holding the bit at `position`, to point errors at.
+    fn digit_at(&self, position: usize) -> &'a str {
+        let index = (position / 4).min(self.line.text.len().saturating_sub(1));
+        &self.line.text[index..index + 1]
+    }
+
+    fn read_packet(&mut self) -> Result<Packet, ParseError> {
+        let version = self.read(3, "packet version")? as u8;
+        let type_start = self.position;
+        let type_id = self.read(3, "packet type")?;
+
+        let operator = match type_id {
+            4 => {
+                return Ok(Packet {
+                    version,
+                    kind: PacketKind::Literal(self.read_literal()?),
+                })
+            }
+            0 => Operator::Sum,
+            1 => Operator::Product,
+            2 => Operator::Minimum,
+            3 => Operator::Maximum,
+            5 => Operator::GreaterThan,
+            6 => Operator::LessThan,
+            _ => Operator::EqualTo,
+        };
+
+        let length_start = self.position;
+        let packets = if self.read(1, "length type")? == 0 {
+            let length = self.read(15, "sub-packet length")? as usize;
+            let end = self.position + length;
+            let mut packets = vec![];
+            while self.position < end {
+                packets.push(self.read_packet()?);
+            }
+            if self.position != end {
+                return Err(self.line.error(
+                    self.digit_at(length_start),
+                    format!("sub-packets spanning {} bits", length),
+                ));
+            }
+            packets
+        } else {
+            let count = self.read(11, "sub-packet count")?;
+            (0..count)
+                .map(|_| self.read_packet())
+                .collect::<Result<_, _>>()?
+        };
+
+        let operands_valid = match operator {
+            Operator::GreaterThan | Operator::LessThan | Operator::EqualTo => packets.len() == 2,
+            _ => !packets.is_empty(),
+        };
+        if !operands_valid {
+            return Err(self
+                .line
+                .error(self.digit_at(type_start), "operator with valid sub-packets"));
+        }
+
+        Ok(Packet {
+            version,
+            kind: PacketKind::Operator(operator, packets),
+        })
+    }
+
+    fn read_literal(&mut self) -> Result<u64, ParseError> {
+        let mut value: u64 = 0;
+        loop {
+            let group = self.read(5, "literal group")?;
+            value = (value << 4) | (group & 0b1111);
+            if group & 0b10000 == 0 {
+                return Ok(value);
+            }
+        }
+    }
+}
+
+fn decode(transmission: &str) -> Result<Packet, ParseError> {
+    let line = SourceLine::new(1, transmission.trim());
+    BitReader::new(line)?.read_packet()
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Packet;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        decode(input)
+    }
+
+    fn part_1(packet: &Self::Input) -> Self::Answer1 {
+        packet.version_sum()
+    }
+
+    fn part_2(packet: &Self::Input) -> Self::Answer2 {
+        packet.evaluate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal() {
+        let packet = decode("D2FE28").unwrap();
+        assert_eq!(
+            Packet {
+                version: 6,
+                kind: PacketKind::Literal(2021),
+            },
+            packet
+        );
+    }
+
+    #[test]
+    fn operators() {
+        let literal = |version, value| Packet {
+            version,
+            kind: PacketKind::Literal(value),
+        };
+
+        // length type 0: total length in bits
+        assert_eq!(
+            Packet {
+                version: 1,
+                kind: PacketKind::Operator(
+                    Operator::LessThan,
+                    vec![literal(6, 10), literal(2, 20)]
+                ),
+            },
+            decode("38006F45291200").unwrap()
+        );
+
+        // length type 1: number of sub-packets
+        assert_eq!(
+            Packet {
+                version: 7,
+                kind: PacketKind::Operator(
+                    Operator::Maximum,
+                    vec![literal(2, 1), literal(4, 2), literal(1, 3)]
+                ),
+            },
+            decode("EE00D40C823060").unwrap()
+        );
+    }
+
+    #[test]
+    fn version_sums() {
+        assert_eq!(16, decode("8A004A801A8002F478").unwrap().version_sum());
+        assert_eq!(
+            12,
+            decode("620080001611562C8802118E34").unwrap().version_sum()
+        );
+        assert_eq!(
+            23,
+            decode("C0015000016115A2E0802F182340")
+                .unwrap()
+                .version_sum()
+        );
+        assert_eq!(
+            31,
+            decode("A0016C880162017C3686B18A3D4780")
+                .unwrap()
+                .version_sum()
+        );
+    }
+
+    #[test]
+    fn evaluate() {
+        assert_eq!(3, decode("C200B40A82").unwrap().evaluate());
+        assert_eq!(54, decode("04005AC33890").unwrap().evaluate());
+        assert_eq!(7, decode("880086C3E88112").unwrap().evaluate());
+        assert_eq!(9, decode("CE00C43D881120").unwrap().evaluate());
+        assert_eq!(1, decode("D8005AC2A8F0").unwrap().evaluate());
+        assert_eq!(0, decode("F600BC2D8F").unwrap().evaluate());
+        assert_eq!(0, decode("9C005AC2F8F0").unwrap().evaluate());
+        assert_eq!(1, decode("9C0141080250320F1802104A08").unwrap().evaluate());
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            ParseError::unexpected(1, 3, "G", "hexadecimal digit"),
+            decode("D2G").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::missing(1, "literal group"),
+            decode("D2FE").unwrap_err()
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use crate::error::{ParseError, SourceLine};
+use crate::solution::Solution;
 use regex::Regex;
 use std::collections::HashSet;
 
@@ -7,11 +9,12 @@ struct State {
     vel: (i32, i32),
 }
 
-struct Target {
+pub struct Target {
     min: (i32, i32),
     max: (i32, i32),
 }
 
+#[allow(dead_code)]
 #[derive(Clone, Copy)]
 struct EndState {
     state: State,
@@ -87,13 +90,15 @@ fn num_possible_start_velocities(target: &Target) -> usize {
     let mut end_states: HashSet<(i32, i32)> = HashSet::default();
     for try_dx in 0..500 {
         for try_dy in -500..500 {
-            if let Some(end_state) = fire(
+            if fire(
                 &State {
                     pos: start_pos,
                     vel: (try_dx, try_dy),
                 },
                 target,
-            ) {
+            )
+            .is_some()
+            {
                 end_states.insert((try_dx, try_dy));
             }
         }
@@ -102,29 +107,64 @@ fn num_possible_start_velocities(target: &Target) -> usize {
     end_states.len()
 }
 
-fn parse_input(string: &str) -> Target {
+fn parse_input(string: &str) -> Result<Target, ParseError> {
     lazy_static! {
         static ref RE: Regex = Regex::new("x=(.+)\\.\\.(.+), y=(.+)\\.\\.(.+)").unwrap();
     }
 
-    let caps = RE.captures(string).unwrap();
+    let line = SourceLine::new(1, string);
+    let caps = RE
+        .captures(string)
+        .ok_or_else(|| line.error(string, "'target area: x=A..B, y=C..D'"))?;
 
-    let parser = |m: regex::Match| {
-        println!("{:?}", m.as_str());
-        m.as_str().parse::<i32>().unwrap()
+    let parser = |i: usize| line.parse::<i32>(caps.get(i).unwrap().as_str(), "coordinate");
+    let x_range = (parser(1)?, parser(2)?);
+    let y_range = (parser(3)?, parser(4)?);
+
+    // the probe only ever flies forward, and falls through the target from
+    // above it
+    let span = |from: usize, to: usize| {
+        &string[caps.get(from).unwrap().start()..caps.get(to).unwrap().end()]
     };
-    let x_range = (caps.get(1).map_or(0, parser), caps.get(2).map_or(0, parser));
-    let y_range = (caps.get(3).map_or(0, parser), caps.get(4).map_or(0, parser));
+    if x_range.0.min(x_range.1) <= 0 {
+        return Err(line.error(span(1, 2), "x range ahead of the probe"));
+    }
+    if y_range.0.max(y_range.1) >= 0 {
+        return Err(line.error(span(3, 4), "y range below the probe"));
+    }
 
-    Target {
+    Ok(Target {
         min: (x_range.0.min(x_range.1), y_range.0.min(y_range.1)),
         max: (x_range.0.max(x_range.1), y_range.0.max(y_range.1)),
+    })
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Target;
+    type Answer1 = String;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input.trim())
+    }
+
+    fn part_1(target: &Self::Input) -> Self::Answer1 {
+        // targets too far off for the velocities tried can't be hit
+        brute_force_trick_shot(target).map_or("none".to_string(), |e| e.highest_y.to_string())
+    }
+
+    fn part_2(target: &Self::Input) -> Self::Answer2 {
+        num_possible_start_velocities(target)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
 
     #[test]
     fn test_step() {
@@ -148,23 +188,45 @@ mod tests {
 
     #[test]
     fn test_trick_shot() {
-        let target = parse_input("target area: x=20..30, y=-10..-5");
+        let target = parse_input(&load(2021, 17, Variant::Example(1)).unwrap()).unwrap();
         let result = brute_force_trick_shot(&target);
 
         assert!(result.is_some());
-        assert_eq!(45, result.unwrap().highest_y);
+        assert_answer(2021, 17, Variant::Example(1), 1, result.unwrap().highest_y);
+
+        let velocities = num_possible_start_velocities(&target);
+        assert_answer(2021, 17, Variant::Example(1), 2, velocities);
+    }
 
-        assert_eq!(112, num_possible_start_velocities(&target));
+    #[test]
+    fn unreachable() {
+        assert_eq!(
+            ParseError::unexpected(1, 16, "-20..-10", "x range ahead of the probe"),
+            Day17::parse("target area: x=-20..-10, y=-10..-5")
+                .err()
+                .unwrap()
+        );
+        assert_eq!(
+            ParseError::unexpected(1, 26, "5..10", "y range below the probe"),
+            Day17::parse("target area: x=20..30, y=5..10")
+                .err()
+                .unwrap()
+        );
+
+        let target = Day17::parse("target area: x=200000..200010, y=-10..-5").unwrap();
+        assert_eq!("none", Day17::part_1(&target));
+        assert_eq!(0, Day17::part_2(&target));
     }
 
     #[test]
     fn actual_trick_shot() {
-        let target = parse_input("target area: x=169..206, y=-108..-68");
+        let target = parse_input(&load(2021, 17, Variant::Real).unwrap()).unwrap();
         let result = brute_force_trick_shot(&target);
 
         assert!(result.is_some());
-        assert_eq!(5778, result.unwrap().highest_y);
+        assert_answer(2021, 17, Variant::Real, 1, result.unwrap().highest_y);
 
-        assert_eq!(2576, num_possible_start_velocities(&target));
+        let velocities = num_possible_start_velocities(&target);
+        assert_answer(2021, 17, Variant::Real, 2, velocities);
     }
 }
@@ -0,0 +1,315 @@
+use crate::error::{source_lines, ParseError, SourceLine};
+use crate::solution::Solution;
+use std::fmt;
+use std::ops::Add;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SnailfishNumber {
+    Regular(u32),
+    Pair(Box<SnailfishNumber>, Box<SnailfishNumber>),
+}
+
+impl SnailfishNumber {
+    fn pair(left: SnailfishNumber, right: SnailfishNumber) -> SnailfishNumber {
+        SnailfishNumber::Pair(Box::new(left), Box::new(right))
+    }
+
+    pub fn magnitude(&self) -> u32 {
+        match self {
+            SnailfishNumber::Regular(value) => *value,
+            SnailfishNumber::Pair(left, right) => 3 * left.magnitude() + 2 * right.magnitude(),
+        }
+    }
+
+    fn reduce(&mut self) {
+        loop {
+            if self.explode(0).is_some() {
+                continue;
+            }
+            if !self.split() {
+                break;
+            }
+        }
+    }
+
+    /// Explodes the leftmost pair nested inside four pairs, returning the values
+    /// still to be added to the regular numbers on its left and right.
+    fn explode(&mut self, depth: usize) -> Option<(u32, u32)> {
+        let (left, right) = match self {
+            SnailfishNumber::Regular(_) => return None,
+            SnailfishNumber::Pair(left, right) => (left, right),
+        };
+
+        if depth >= 4 {
+            if let (SnailfishNumber::Regular(a), SnailfishNumber::Regular(b)) = (&**left, &**right)
+            {
+                let carry = (*a, *b);
+                *self = SnailfishNumber::Regular(0);
+                return Some(carry);
+            }
+        }
+
+        if let Some((a, b)) = left.explode(depth + 1) {
+            right.add_leftmost(b);
+            return Some((a, 0));
+        }
+        if let Some((a, b)) = right.explode(depth + 1) {
+            left.add_rightmost(a);
+            return Some((0, b));
+        }
+        None
+    }
+
+    fn add_leftmost(&mut self, carry: u32) {
+        match self {
+            SnailfishNumber::Regular(value) => *value += carry,
+            SnailfishNumber::Pair(left, _) => left.add_leftmost(carry),
+        }
+    }
+
+    fn add_rightmost(&mut self, carry: u32) {
+        match self {
+            SnailfishNumber::Regular(value) => *value += carry,
+            SnailfishNumber::Pair(_, right) => right.add_rightmost(carry),
+        }
+    }
+
+    /// Splits the leftmost regular number of 10 or more into a pair.
+    fn split(&mut self) -> bool {
+        match self {
+            SnailfishNumber::Regular(value) if *value >= 10 => {
+                *self = SnailfishNumber::pair(
+                    SnailfishNumber::Regular(*value / 2),
+                    SnailfishNumber::Regular(*value - *value / 2),
+                );
+                true
+            }
+            SnailfishNumber::Regular(_) => false,
+            SnailfishNumber::Pair(left, right) => left.split() || right.split(),
+        }
+    }
+
+    fn parse(line: &SourceLine, position: &mut usize) -> Result<SnailfishNumber, ParseError> {
+        let rest = &line.text[*position..];
+        if rest.starts_with('[') {
+            *position += 1;
+            let left = SnailfishNumber::parse(line, position)?;
+            expect(line, position, ',')?;
+            let right = SnailfishNumber::parse(line, position)?;
+            expect(line, position, ']')?;
+            return Ok(SnailfishNumber::pair(left, right));
+        }
+
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits == 0 {
+            return Err(line.error(first_char(rest), "'[' or regular number"));
+        }
+        *position += digits;
+        line.parse::<u32>(&rest[..digits], "regular number")
+            .map(SnailfishNumber::Regular)
+    }
+}
+
+/// The first character of `s`, or `s` itself if it is empty.
+fn first_char(s: &str) -> &str {
+    &s[..s.chars().next().map_or(0, char::len_utf8)]
+}
+
+fn expect(line: &SourceLine, position: &mut usize, token: char) -> Result<(), ParseError> {
+    let rest = &line.text[*position..];
+    if !rest.starts_with(token) {
+        return Err(line.error(first_char(rest), format!("'{}'", token)));
+    }
+    *position += 1;
+    Ok(())
+}
+
+impl Add for SnailfishNumber {
+    type Output = SnailfishNumber;
+
+    fn add(self, other: SnailfishNumber) -> SnailfishNumber {
+        let mut sum = SnailfishNumber::pair(self, other);
+        sum.reduce();
+        sum
+    }
+}
+
+impl fmt::Display for SnailfishNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnailfishNumber::Regular(value) => write!(f, "{}", value),
+            SnailfishNumber::Pair(left, right) => write!(f, "[{},{}]", left, right),
+        }
+    }
+}
+
+impl std::str::FromStr for SnailfishNumber {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let line = SourceLine::new(1, s);
+        if !s.starts_with('[') {
+            return Err(line.error(first_char(s), "'['"));
+        }
+
+        let mut position = 0;
+        let number = SnailfishNumber::parse(&line, &mut position)?;
+        if position < s.len() {
+            return Err(line.error(&s[position..], "end of line"));
+        }
+        Ok(number)
+    }
+}
+
+fn sum(numbers: &[SnailfishNumber]) -> Option<SnailfishNumber> {
+    numbers.iter().cloned().reduce(|acc, n| acc + n)
+}
+
+fn largest_pairwise_magnitude(numbers: &[SnailfishNumber]) -> u32 {
+    let mut largest = 0;
+    for (i, a) in numbers.iter().enumerate() {
+        for (j, b) in numbers.iter().enumerate() {
+            if i != j {
+                largest = largest.max((a.clone() + b.clone()).magnitude());
+            }
+        }
+    }
+
+    largest
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<SnailfishNumber>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let numbers = source_lines(input)
+            .map(|line| {
+                line.text
+                    .parse::<SnailfishNumber>()
+                    .map_err(|e| e.offset_lines(line.number - 1))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if numbers.is_empty() {
+            return Err(ParseError::missing(1, "snailfish number"));
+        }
+        Ok(numbers)
+    }
+
+    fn part_1(numbers: &Self::Input) -> Self::Answer1 {
+        sum(numbers).map_or(0, |n| n.magnitude())
+    }
+
+    fn part_2(numbers: &Self::Input) -> Self::Answer2 {
+        largest_pairwise_magnitude(numbers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
+
+    fn number(s: &str) -> SnailfishNumber {
+        s.parse::<SnailfishNumber>().unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        for s in [
+            "[1,2]",
+            "[[1,2],3]",
+            "[9,[8,7]]",
+            "[[[[1,3],[5,3]],[[1,3],[8,7]]],[[[4,9],[6,9]],[[8,2],[7,3]]]]",
+        ] {
+            assert_eq!(s, number(s).to_string());
+        }
+    }
+
+    #[test]
+    fn explode() {
+        for (before, after) in [
+            ("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]"),
+            ("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]"),
+            ("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]"),
+            (
+                "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+            ),
+        ] {
+            let mut n = number(before);
+            assert!(n.explode(0).is_some());
+            assert_eq!(after, n.to_string());
+        }
+    }
+
+    #[test]
+    fn add() {
+        assert_eq!(
+            "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]",
+            (number("[[[[4,3],4],4],[7,[[8,4],9]]]") + number("[1,1]")).to_string()
+        );
+
+        let numbers = Day18::parse(
+            "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]
+[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]
+[[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]
+[[[[2,4],7],[6,[0,5]]],[[[6,8],[2,8]],[[2,1],[4,5]]]]
+[7,[5,[[3,8],[1,4]]]]
+[[2,[2,2]],[8,[8,1]]]
+[2,9]
+[1,[[[9,3],9],[[9,0],[0,7]]]]
+[[[5,[7,4]],7],1]
+[[[[4,2],2],6],[8,7]]",
+        )
+        .unwrap();
+        assert_eq!(
+            "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]",
+            sum(&numbers).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn magnitude() {
+        assert_eq!(143, number("[[1,2],[[3,4],5]]").magnitude());
+        assert_eq!(
+            3488,
+            number("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]").magnitude()
+        );
+    }
+
+    #[test]
+    fn example() {
+        let numbers = Day18::parse(&load(2021, 18, Variant::Example(1)).unwrap()).unwrap();
+
+        assert_eq!(
+            "[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]",
+            sum(&numbers).unwrap().to_string()
+        );
+        assert_answer(2021, 18, Variant::Example(1), 1, Day18::part_1(&numbers));
+        assert_answer(2021, 18, Variant::Example(1), 2, Day18::part_2(&numbers));
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            ParseError::unexpected(2, 4, "]", "','"),
+            Day18::parse("[1,2]\n[[1]]").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::missing(1, "']'"),
+            Day18::parse("[[1,2],3").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::unexpected(1, 6, "x", "end of line"),
+            "[1,2]x".parse::<SnailfishNumber>().unwrap_err()
+        );
+    }
+}
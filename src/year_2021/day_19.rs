@@ -0,0 +1,319 @@
+use crate::common::{rotations, Point3};
+use crate::error::{source_lines, ParseError, SourceLine};
+use crate::solution::Solution;
+use std::cell::OnceCell;
+use std::collections::{HashMap, HashSet};
+
+/// Two scanners are aligned once this many of their beacons line up.
+const MIN_OVERLAP: usize = 12;
+
+/// Beacon positions reported by one scanner, relative to itself and in its
+/// own orientation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scanner {
+    beacons: Vec<Point3>,
+}
+
+/// The parsed scanner reports, and their alignment once a part has asked
+/// for it, so both parts share the one search.
+#[derive(Debug)]
+pub struct Report {
+    scanners: Vec<Scanner>,
+    alignment: OnceCell<Result<Alignment, usize>>,
+}
+
+impl Report {
+    /// The scanners placed relative to scanner 0, or the number of the first
+    /// scanner that shares too few beacons with the others.
+    fn alignment(&self) -> &Result<Alignment, usize> {
+        self.alignment.get_or_init(|| align(&self.scanners))
+    }
+
+    /// Answers a part from the alignment, or says which scanner is left over.
+    fn answer<T: ToString>(&self, part: impl Fn(&Alignment) -> T) -> String {
+        match self.alignment() {
+            Ok(alignment) => part(alignment).to_string(),
+            Err(scanner) => format!(
+                "none, scanner {} shares fewer than {} beacons with the others",
+                scanner, MIN_OVERLAP
+            ),
+        }
+    }
+}
+
+/// Scanners and beacons placed relative to scanner 0.
+#[derive(Debug)]
+pub struct Alignment {
+    pub scanners: Vec<Point3>,
+    pub beacons: HashSet<Point3>,
+}
+
+impl Alignment {
+    pub fn max_scanner_distance(&self) -> i64 {
+        self.scanners
+            .iter()
+            .flat_map(|a| self.scanners.iter().map(move |b| a.manhattan_distance(b)))
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// Finds the orientation and position of `scanner` at which at least
+/// `MIN_OVERLAP` of its beacons coincide with `reference`, returning the
+/// scanner position and its beacons placed in the frame of `reference`.
+fn find_overlap(
+    reference: &[Point3],
+    scanner: &Scanner,
+    rotations: &[[[i64; 3]; 3]],
+) -> Option<(Point3, Vec<Point3>)> {
+    for rotation in rotations {
+        let rotated = scanner
+            .beacons
+            .iter()
+            .map(|b| b.rotate(rotation))
+            .collect::<Vec<_>>();
+
+        let mut offsets: HashMap<Point3, usize> = HashMap::new();
+        for a in reference {
+            for b in rotated.iter() {
+                *offsets.entry(*a - *b).or_default() += 1;
+            }
+        }
+
+        if let Some((&offset, _)) = offsets.iter().find(|(_, &n)| n >= MIN_OVERLAP) {
+            return Some((offset, rotated.into_iter().map(|b| b + offset).collect()));
+        }
+    }
+
+    None
+}
+
+/// Places every scanner relative to scanner 0, or returns the number of the
+/// first scanner that does not overlap with the others.
+fn align(scanners: &[Scanner]) -> Result<Alignment, usize> {
+    let rotations = rotations();
+    let mut positions = vec![None; scanners.len()];
+    let mut placed = vec![vec![]; scanners.len()];
+
+    positions[0] = Some(Point3::default());
+    placed[0] = scanners[0].beacons.clone();
+
+    // every scanner is compared against the others once, right after it is placed
+    let mut frontier = vec![0];
+    while let Some(reference) = frontier.pop() {
+        for i in 0..scanners.len() {
+            if positions[i].is_some() {
+                continue;
+            }
+            if let Some((position, beacons)) =
+                find_overlap(&placed[reference], &scanners[i], &rotations)
+            {
+                positions[i] = Some(position);
+                placed[i] = beacons;
+                frontier.push(i);
+            }
+        }
+    }
+
+    if let Some(i) = positions.iter().position(Option::is_none) {
+        return Err(i);
+    }
+    Ok(Alignment {
+        scanners: positions.into_iter().flatten().collect(),
+        beacons: placed.into_iter().flatten().collect(),
+    })
+}
+
+fn parse_beacon(line: &SourceLine) -> Result<Point3, ParseError> {
+    let coordinates = line
+        .text
+        .split(',')
+        .map(|token| line.parse::<i64>(token, "coordinate"))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match coordinates[..] {
+        [x, y, z] => Ok(Point3::new(x, y, z)),
+        _ => Err(line.error(line.text, "'X,Y,Z'")),
+    }
+}
+
+fn parse_scanners(input: &str) -> Result<Vec<Scanner>, ParseError> {
+    let mut scanners: Vec<Scanner> = vec![];
+    let mut in_report = false;
+
+    for line in source_lines(input) {
+        if line.text.is_empty() {
+            in_report = false;
+        } else if line.text.starts_with("---") {
+            let expected = format!("'--- scanner {} ---'", scanners.len());
+            let number = line
+                .text
+                .strip_prefix("--- scanner ")
+                .and_then(|s| s.strip_suffix(" ---"))
+                .ok_or_else(|| line.error(line.text, expected.as_str()))?;
+            if line.parse::<usize>(number, &expected)? != scanners.len() {
+                return Err(line.error(number, expected));
+            }
+
+            scanners.push(Scanner { beacons: vec![] });
+            in_report = true;
+        } else if in_report {
+            let beacon = parse_beacon(&line)?;
+            scanners.last_mut().unwrap().beacons.push(beacon);
+        } else {
+            return Err(line.error(line.text, "'--- scanner N ---'"));
+        }
+    }
+
+    if scanners.is_empty() {
+        return Err(ParseError::missing(1, "'--- scanner 0 ---'"));
+    }
+    Ok(scanners)
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Report;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Report {
+            scanners: parse_scanners(input)?,
+            alignment: OnceCell::new(),
+        })
+    }
+
+    fn part_1(report: &Self::Input) -> Self::Answer1 {
+        report.answer(|alignment| alignment.beacons.len())
+    }
+
+    fn part_2(report: &Self::Input) -> Self::Answer2 {
+        report.answer(Alignment::max_scanner_distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
+
+    /// Builds the report of scanners at `positions`, each turned by a different
+    /// rotation, over a field of beacons where neighbouring scanners share
+    /// at least `MIN_OVERLAP` of them.
+    fn survey(positions: &[Point3]) -> (String, HashSet<Point3>) {
+        let mut seed: i64 = 7;
+        let mut random = move |range: i64| {
+            seed = (seed * 1_103_515_245 + 12_345) % 2_147_483_648;
+            seed % (2 * range + 1) - range
+        };
+
+        let mut beacons = HashSet::new();
+        for (i, position) in positions.iter().enumerate() {
+            for _ in 0..15 {
+                let offset = Point3::new(random(1000), random(1000), random(1000));
+                beacons.insert(*position + offset);
+            }
+            if let Some(next) = positions.get(i + 1) {
+                // halfway between the two scanners is in range of both
+                let middle = Point3::new(
+                    (position.x + next.x) / 2,
+                    (position.y + next.y) / 2,
+                    (position.z + next.z) / 2,
+                );
+                for _ in 0..MIN_OVERLAP {
+                    let offset = Point3::new(random(300), random(300), random(300));
+                    beacons.insert(middle + offset);
+                }
+            }
+        }
+
+        let rotations = rotations();
+        let in_range = |a: &Point3, b: &Point3| {
+            (a.x - b.x).abs() <= 1000 && (a.y - b.y).abs() <= 1000 && (a.z - b.z).abs() <= 1000
+        };
+        let mut report = vec![];
+        for (i, position) in positions.iter().enumerate() {
+            report.push(format!("--- scanner {} ---", i));
+            let rotation = &rotations[i * 5 % 24];
+            for beacon in beacons.iter().filter(|b| in_range(b, position)) {
+                let seen = (*beacon - *position).rotate(rotation);
+                report.push(format!("{},{},{}", seen.x, seen.y, seen.z));
+            }
+            report.push(String::new());
+        }
+
+        (report.join("\n"), beacons)
+    }
+
+    #[test]
+    fn orientations() {
+        let a =
+            parse_scanners("--- scanner 0 ---\n-1,-1,1\n-2,-2,2\n-3,-3,3\n-2,-3,1\n5,6,-4\n8,0,7")
+                .unwrap();
+        let b =
+            parse_scanners("--- scanner 0 ---\n1,-1,1\n2,-2,2\n3,-3,3\n2,-1,3\n-5,4,-6\n-8,-7,0")
+                .unwrap();
+
+        assert!(rotations().iter().any(|r| a[0]
+            .beacons
+            .iter()
+            .map(|p| p.rotate(r))
+            .eq(b[0].beacons.iter().copied())));
+    }
+
+    #[test]
+    fn alignment() {
+        let positions = [
+            Point3::new(0, 0, 0),
+            Point3::new(1200, -100, 150),
+            Point3::new(2300, 200, -1000),
+            Point3::new(1000, 1300, -1100),
+            Point3::new(-50, 2400, -900),
+        ];
+        let (report, beacons) = survey(&positions);
+        let report = Day19::parse(&report).unwrap();
+
+        let alignment = report.alignment().as_ref().unwrap();
+        assert_eq!(positions.to_vec(), alignment.scanners);
+        assert_eq!(beacons, alignment.beacons);
+        assert_eq!(beacons.len().to_string(), Day19::part_1(&report));
+        assert_eq!("4800", Day19::part_2(&report));
+    }
+
+    #[test]
+    fn disconnected() {
+        let (report, _) = survey(&[Point3::new(0, 0, 0), Point3::new(5000, 0, 0)]);
+        let report = Day19::parse(&report).unwrap();
+        assert_eq!(Err(1), align(&report.scanners).map(|_| ()));
+        assert_eq!(
+            "none, scanner 1 shares fewer than 12 beacons with the others",
+            Day19::part_1(&report)
+        );
+    }
+
+    #[test]
+    fn example() {
+        let report = Day19::parse(&load(2021, 19, Variant::Example(1)).unwrap()).unwrap();
+        assert_answer(2021, 19, Variant::Example(1), 1, Day19::part_1(&report));
+        assert_answer(2021, 19, Variant::Example(1), 2, Day19::part_2(&report));
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            ParseError::unexpected(3, 13, "2", "'--- scanner 1 ---'"),
+            Day19::parse("--- scanner 0 ---\n1,2,3\n--- scanner 2 ---").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::unexpected(2, 1, "1,2", "'X,Y,Z'"),
+            Day19::parse("--- scanner 0 ---\n1,2").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::unexpected(1, 1, "1,2,3", "'--- scanner N ---'"),
+            Day19::parse("1,2,3").unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use crate::error::{source_lines, ParseError, SourceLine};
+use crate::solution::Solution;
+pub enum Command {
+    Forward(i128),
+    Up(i128),
+    Down(i128),
+}
+
+impl std::str::FromStr for Command {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let line = SourceLine::new(1, s);
+        let mut command = s.split_whitespace();
+        let direction = command
+            .next()
+            .ok_or_else(|| ParseError::missing(1, "direction"))?;
+        let distance = command
+            .next()
+            .ok_or_else(|| ParseError::missing(1, "distance"))?;
+        let distance = line.parse::<i128>(distance, "distance")?;
+
+        match direction {
+            "forward" => Ok(Command::Forward(distance)),
+            "up" => Ok(Command::Up(distance)),
+            "down" => Ok(Command::Down(distance)),
+            _ => Err(line.error(direction, "'forward', 'up' or 'down'")),
+        }
+    }
+}
+
+pub fn get_position(input: &[Command]) -> i128 {
+    let mut horizontal_position: i128 = 0;
+    let mut depth: i128 = 0;
+    for command in input {
+        match command {
+            Command::Forward(x) => horizontal_position += x,
+            Command::Up(x) => depth -= x,
+            Command::Down(x) => depth += x,
+        }
+    }
+
+    horizontal_position * depth
+}
+
+pub fn get_final_position(input: &[Command]) -> i128 {
+    let mut horizontal_position: i128 = 0;
+    let mut depth: i128 = 0;
+    let mut aim: i128 = 0;
+    for command in input {
+        match command {
+            Command::Forward(x) => {
+                horizontal_position += x;
+                depth += x * aim;
+            }
+            Command::Up(x) => aim -= x,
+            Command::Down(x) => aim += x,
+        }
+    }
+
+    horizontal_position * depth
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Command>;
+    type Answer1 = i128;
+    type Answer2 = i128;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        source_lines(input)
+            .map(|line| {
+                line.text
+                    .parse::<Command>()
+                    .map_err(|e| e.offset_lines(line.number - 1))
+            })
+            .collect()
+    }
+
+    fn part_1(commands: &Self::Input) -> Self::Answer1 {
+        get_position(commands)
+    }
+
+    fn part_2(commands: &Self::Input) -> Self::Answer2 {
+        get_final_position(commands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
+
+    #[test]
+    fn day_2() {
+        let file = &load(2021, 2, Variant::Real).unwrap();
+        let command_vec: Vec<super::Command> = file
+            .lines()
+            .map(|x| x.parse::<super::Command>().unwrap())
+            .collect();
+
+        let position = super::get_position(&command_vec);
+        assert_answer(2021, 2, Variant::Real, 1, position);
+
+        let position = super::get_final_position(&command_vec);
+        assert_answer(2021, 2, Variant::Real, 2, position);
+    }
+
+    #[test]
+    fn malformed_command() {
+        use super::{Day2, ParseError, Solution};
+
+        assert_eq!(
+            ParseError::unexpected(2, 1, "backward", "'forward', 'up' or 'down'"),
+            Day2::parse("forward 5\nbackward 3").err().unwrap()
+        );
+        assert_eq!(
+            ParseError::missing(3, "distance"),
+            Day2::parse("forward 5\ndown 3\nup").err().unwrap()
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use crate::common::{Grid, Topology};
+use crate::error::{ParseError, SourceLine};
+use crate::solution::Solution;
+
+/// An image that extends forever, with every pixel outside `pixels` set to
+/// `background`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pixels: Grid<bool>,
+    background: bool,
+}
+
+impl Image {
+    /// Applies the enhancement algorithm once. The image grows by one pixel on
+    /// every side, and the background becomes whatever the algorithm turns a
+    /// block of background pixels into, which flips every pass when
+    /// `algorithm[0]` is lit and `algorithm[511]` is not.
+    pub fn enhance(&self, algorithm: &[bool]) -> Image {
+        let topology = Topology::Infinite(self.background);
+        let width = self.pixels.width + 2;
+        let height = self.pixels.height + 2;
+
+        let mut cells = Vec::with_capacity(width * height);
+        for y in -1..height as i32 - 1 {
+            for x in -1..width as i32 - 1 {
+                let mut index = 0;
+                for dy in -1..=1 {
+                    for dx in -1..=1 {
+                        let lit = self.pixels.value_at(&(x + dx, y + dy), &topology) == Some(&true);
+                        index = (index << 1) | lit as usize;
+                    }
+                }
+                cells.push(algorithm[index]);
+            }
+        }
+
+        Image {
+            pixels: Grid::new(width, height, cells),
+            background: algorithm[if self.background { 511 } else { 0 }],
+        }
+    }
+
+    /// Number of lit pixels, or `None` if infinitely many are lit.
+    pub fn lit_count(&self) -> Option<usize> {
+        if self.background {
+            None
+        } else {
+            Some(self.pixels.cells.iter().filter(|&&lit| lit).count())
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TrenchMap {
+    algorithm: Vec<bool>,
+    image: Image,
+}
+
+impl TrenchMap {
+    fn enhanced(&self, passes: usize) -> Image {
+        (0..passes).fold(self.image.clone(), |image, _| {
+            image.enhance(&self.algorithm)
+        })
+    }
+}
+
+fn pixel(c: char) -> Option<bool> {
+    match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    }
+}
+
+impl std::str::FromStr for TrenchMap {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut sections = s.splitn(3, '\n');
+        let line = SourceLine::new(1, sections.next().unwrap_or_default().trim_end());
+        let algorithm = line
+            .text
+            .char_indices()
+            .map(|(i, c)| {
+                pixel(c).ok_or_else(|| line.error(&line.text[i..i + c.len_utf8()], "'#' or '.'"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if algorithm.len() != 512 {
+            return Err(line.error(line.text, "512 pixel algorithm"));
+        }
+        // a lit background would light every dark pixel and stay lit, leaving
+        // infinitely many pixels on after any number of passes
+        if algorithm[0] && algorithm[511] {
+            return Err(line.error(&line.text[511..], "'.' when the first pixel is '#'"));
+        }
+
+        let separator = SourceLine::new(2, sections.next().unwrap_or_default().trim_end());
+        if !separator.text.is_empty() {
+            return Err(separator.error(separator.text, "empty line"));
+        }
+
+        let pixels = Grid::parse(sections.next().unwrap_or_default(), "'#' or '.'", pixel)
+            .map_err(|e| e.offset_lines(2))?;
+
+        Ok(TrenchMap {
+            algorithm,
+            image: Image {
+                pixels,
+                background: false,
+            },
+        })
+    }
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = TrenchMap;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse::<TrenchMap>()
+    }
+
+    fn part_1(map: &Self::Input) -> Self::Answer1 {
+        map.enhanced(2).lit_count().unwrap()
+    }
+
+    fn part_2(map: &Self::Input) -> Self::Answer2 {
+        map.enhanced(50).lit_count().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
+
+    #[test]
+    fn example() {
+        let map = Day20::parse(&load(2021, 20, Variant::Example(1)).unwrap()).unwrap();
+
+        assert_eq!(Some(10), map.image.lit_count());
+        assert_answer(2021, 20, Variant::Example(1), 1, Day20::part_1(&map));
+        assert_answer(2021, 20, Variant::Example(1), 2, Day20::part_2(&map));
+    }
+
+    #[test]
+    fn flipping_background() {
+        // every pixel becomes the opposite of what it was
+        let inverter = (0..512)
+            .map(|i| if i & 0b10000 == 0 { '#' } else { '.' })
+            .collect::<String>();
+        let map = Day20::parse(&format!("{}\n\n#..\n.#.\n..#", inverter)).unwrap();
+
+        let once = map.enhanced(1);
+        assert!(once.background);
+        assert_eq!(None, once.lit_count());
+
+        let twice = map.enhanced(2);
+        assert!(!twice.background);
+        assert_eq!(Some(3), twice.lit_count());
+        assert_eq!(3, Day20::part_1(&map));
+    }
+
+    #[test]
+    fn lit_background() {
+        let algorithm = "#".repeat(512);
+        assert_eq!(
+            ParseError::unexpected(1, 512, "#", "'.' when the first pixel is '#'"),
+            Day20::parse(&format!("{}\n\n#..\n.#.\n..#", algorithm)).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        let input = &load(2021, 20, Variant::Example(1)).unwrap();
+        assert_eq!(
+            ParseError::unexpected(4, 3, "x", "'#' or '.'"),
+            Day20::parse(&input.replacen("\n#....\n", "\n#.x..\n", 1)).unwrap_err()
+        );
+        assert_eq!(
+            ParseError::unexpected(1, 1, "#.#", "512 pixel algorithm"),
+            Day20::parse("#.#\n\n#").unwrap_err()
+        );
+    }
+}
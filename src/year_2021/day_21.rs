@@ -1,3 +1,5 @@
+use crate::error::{source_lines, ParseError};
+use crate::solution::Solution;
 use std::collections::HashMap;
 
 fn part_1(starting_pos: [u8; 2]) -> u32 {
@@ -43,9 +45,9 @@ fn roll(state: &mut State, win_cache: &mut WinCache) -> u64 {
         return *win_cache.entry(*state).or_default();
     }
 
-    if state.roll_count > 0 && state.roll_count % 3 == 0 {
+    if state.roll_count > 0 && state.roll_count.is_multiple_of(3) {
         // update score
-        if state.roll_count % 2 != 0 {
+        if !state.roll_count.is_multiple_of(2) {
             state.pos[0] = (state.pos[0] + state.die_sum) % 10;
             state.score[0] = state.score[0] + state.pos[0] + 1;
         } else {
@@ -91,25 +93,65 @@ fn roll_with_start_pos(starting_pos: [u8; 2], player_0: bool) -> u64 {
     roll(&mut starting_state, &mut cache)
 }
 
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = [u8; 2];
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut starting_pos = [0; 2];
+        let mut lines = source_lines(input);
+        for (player, pos) in starting_pos.iter_mut().enumerate() {
+            let line = lines
+                .next()
+                .ok_or_else(|| ParseError::missing(player + 1, "starting position"))?;
+            let (_, value) = line.split_once("starting position: ")?;
+            *pos = line.parse::<u8>(value, "starting position 1 to 10")?;
+            if !(1..=10).contains(pos) {
+                return Err(line.error(value, "starting position 1 to 10"));
+            }
+        }
+
+        Ok(starting_pos)
+    }
+
+    fn part_1(starting_pos: &Self::Input) -> Self::Answer1 {
+        part_1(*starting_pos)
+    }
+
+    fn part_2(starting_pos: &Self::Input) -> Self::Answer2 {
+        roll_with_start_pos(*starting_pos, true).max(roll_with_start_pos(*starting_pos, false))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
 
     #[test]
     fn example() {
-        let test1 = part_1([4, 8]);
-        assert_eq!(739785, test1);
+        let start = Day21::parse(&load(2021, 21, Variant::Example(1)).unwrap()).unwrap();
+        assert_eq!([4, 8], start);
 
-        let test2 = roll_with_start_pos([4, 8], true).max(roll_with_start_pos([4, 8], false));
-        assert_eq!(444356092776315, test2);
+        let test1 = part_1(start);
+        assert_answer(2021, 21, Variant::Example(1), 1, test1);
+
+        let test2 = roll_with_start_pos(start, true).max(roll_with_start_pos(start, false));
+        assert_answer(2021, 21, Variant::Example(1), 2, test2);
     }
 
     #[test]
     fn actual() {
-        let test1 = part_1([8, 1]);
-        assert_eq!(518418, test1);
+        let start = Day21::parse(&load(2021, 21, Variant::Real).unwrap()).unwrap();
+
+        let test1 = part_1(start);
+        assert_answer(2021, 21, Variant::Real, 1, test1);
 
-        let test2 = roll_with_start_pos([8, 1], true).max(roll_with_start_pos([8, 1], false));
-        assert_eq!(444356092776315, test2);
+        let test2 = roll_with_start_pos(start, true).max(roll_with_start_pos(start, false));
+        assert_answer(2021, 21, Variant::Real, 2, test2);
     }
 }
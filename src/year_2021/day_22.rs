@@ -0,0 +1,339 @@
+use crate::common::Point3;
+use crate::error::{source_lines, ParseError, SourceLine};
+use crate::solution::Solution;
+use regex::Regex;
+
+/// The cubes from `min` to `max`, both inclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+/// The coordinate of `point` along axis 0, 1 or 2 for x, y or z.
+fn coordinate(point: &mut Point3, axis: usize) -> &mut i64 {
+    match axis {
+        0 => &mut point.x,
+        1 => &mut point.y,
+        _ => &mut point.z,
+    }
+}
+
+impl Cuboid {
+    pub fn new(min: Point3, max: Point3) -> Cuboid {
+        Cuboid { min, max }
+    }
+
+    /// Whether the cuboid holds no cubes, as when a bound is past the other.
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    pub fn volume(&self) -> i64 {
+        if self.is_empty() {
+            return 0;
+        }
+        let size = self.max - self.min;
+        (size.x + 1) * (size.y + 1) * (size.z + 1)
+    }
+
+    pub fn contains(&self, point: Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let min = Point3::new(
+            self.min.x.max(other.min.x),
+            self.min.y.max(other.min.y),
+            self.min.z.max(other.min.z),
+        );
+        let max = Point3::new(
+            self.max.x.min(other.max.x),
+            self.max.y.min(other.max.y),
+            self.max.z.min(other.max.z),
+        );
+        let overlap = Cuboid::new(min, max);
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    /// Splits the parts of the cuboid outside `other` into at most six
+    /// disjoint cuboids: slabs on either side of the overlap along x, then
+    /// along y within its x range, then along z within both.
+    pub fn difference(&self, other: &Cuboid) -> Vec<Cuboid> {
+        let overlap = match self.intersection(other) {
+            Some(overlap) => overlap,
+            None => return vec![*self],
+        };
+        let mut pieces = Vec::new();
+        let mut rest = *self;
+        for axis in 0..3 {
+            let mut overlap = overlap;
+            let low = *coordinate(&mut overlap.min, axis);
+            let high = *coordinate(&mut overlap.max, axis);
+            let mut below = rest;
+            *coordinate(&mut below.max, axis) = low - 1;
+            let mut above = rest;
+            *coordinate(&mut above.min, axis) = high + 1;
+            pieces.extend(IntoIterator::into_iter([below, above]).filter(|c| !c.is_empty()));
+            *coordinate(&mut rest.min, axis) = low;
+            *coordinate(&mut rest.max, axis) = high;
+        }
+        pieces
+    }
+
+    /// Every cube in the cuboid, in x, y, z order.
+    pub fn points(&self) -> impl Iterator<Item = Point3> {
+        let Cuboid { min, max } = *self;
+        (min.x..=max.x).flat_map(move |x| {
+            (min.y..=max.y).flat_map(move |y| (min.z..=max.z).map(move |z| Point3::new(x, y, z)))
+        })
+    }
+}
+
+/// A region of space held as disjoint cuboids, so its volume is their sum.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CuboidSet {
+    cuboids: Vec<Cuboid>,
+}
+
+impl CuboidSet {
+    pub fn new() -> CuboidSet {
+        CuboidSet::default()
+    }
+
+    /// Adds every cube of `cuboid` to the set.
+    pub fn insert(&mut self, cuboid: Cuboid) {
+        if cuboid.is_empty() {
+            return;
+        }
+        self.remove(cuboid);
+        self.cuboids.push(cuboid);
+    }
+
+    /// Removes every cube of `cuboid` from the set.
+    pub fn remove(&mut self, cuboid: Cuboid) {
+        let cuboids = std::mem::take(&mut self.cuboids);
+        self.cuboids = cuboids
+            .into_iter()
+            .flat_map(|c| c.difference(&cuboid))
+            .collect();
+    }
+
+    pub fn union(&self, other: &CuboidSet) -> CuboidSet {
+        let mut union = self.clone();
+        for &cuboid in &other.cuboids {
+            union.insert(cuboid);
+        }
+        union
+    }
+
+    pub fn subtract(&self, other: &CuboidSet) -> CuboidSet {
+        let mut difference = self.clone();
+        for &cuboid in &other.cuboids {
+            difference.remove(cuboid);
+        }
+        difference
+    }
+
+    pub fn intersect(&self, other: &CuboidSet) -> CuboidSet {
+        // overlaps of two sets of disjoint cuboids are disjoint themselves
+        let cuboids = self
+            .cuboids
+            .iter()
+            .flat_map(|a| other.cuboids.iter().filter_map(move |b| a.intersection(b)))
+            .collect();
+        CuboidSet { cuboids }
+    }
+
+    pub fn volume(&self) -> i64 {
+        self.cuboids.iter().map(Cuboid::volume).sum()
+    }
+
+    pub fn contains(&self, point: Point3) -> bool {
+        self.cuboids.iter().any(|c| c.contains(point))
+    }
+
+    /// The disjoint cuboids that make up the set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &Cuboid> {
+        self.cuboids.iter()
+    }
+
+    /// Every cube in the set.
+    pub fn points(&self) -> impl Iterator<Item = Point3> + '_ {
+        self.cuboids.iter().flat_map(Cuboid::points)
+    }
+}
+
+impl From<Cuboid> for CuboidSet {
+    fn from(cuboid: Cuboid) -> CuboidSet {
+        let mut set = CuboidSet::new();
+        set.insert(cuboid);
+        set
+    }
+}
+
+pub struct Step {
+    cuboid: Cuboid,
+    on: bool,
+}
+
+/// The cubes considered while initializing the reactor.
+fn initialization_region() -> Cuboid {
+    Cuboid::new(Point3::new(-50, -50, -50), Point3::new(50, 50, 50))
+}
+
+fn parse_step(step: &SourceLine) -> Result<Step, ParseError> {
+    lazy_static! {
+        static ref RE: Regex =
+            Regex::new(r"(\w+) x=(.+)\.\.(.+),y=(.+)\.\.(.+),z=(.+)\.\.(.+)").unwrap();
+    }
+    let caps = RE
+        .captures(step.text)
+        .ok_or_else(|| step.error(step.text, "'on|off x=A..B,y=C..D,z=E..F'"))?;
+
+    let parser = |i: usize| step.parse::<i64>(caps.get(i).unwrap().as_str(), "coordinate");
+    let on = match &caps[1] {
+        "on" => true,
+        "off" => false,
+        _ => return Err(step.error(caps.get(1).unwrap().as_str(), "'on' or 'off'")),
+    };
+
+    let min = Point3::new(parser(2)?, parser(4)?, parser(6)?);
+    let max = Point3::new(parser(3)?, parser(5)?, parser(7)?);
+    Ok(Step {
+        cuboid: Cuboid::new(min, max),
+        on,
+    })
+}
+
+fn parse_steps(instructions: &str) -> Result<Vec<Step>, ParseError> {
+    source_lines(instructions).map(|l| parse_step(&l)).collect()
+}
+
+/// The cubes left on after running `steps` on a reactor that starts all off.
+fn reboot<'a>(steps: impl IntoIterator<Item = &'a Step>) -> CuboidSet {
+    let mut on = CuboidSet::new();
+    for step in steps {
+        if step.on {
+            on.insert(step.cuboid);
+        } else {
+            on.remove(step.cuboid);
+        }
+    }
+    on
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Vec<Step>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_steps(input)
+    }
+
+    fn part_1(steps: &Self::Input) -> Self::Answer1 {
+        let region = initialization_region();
+        // steps entirely outside the region can't change anything inside it
+        let inside = steps
+            .iter()
+            .filter(|s| s.cuboid.intersection(&region).is_some());
+        reboot(inside).intersect(&region.into()).volume()
+    }
+
+    fn part_2(steps: &Self::Input) -> Self::Answer2 {
+        reboot(steps).volume()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
+    use std::collections::HashSet;
+
+    fn cuboid(min: (i64, i64, i64), max: (i64, i64, i64)) -> Cuboid {
+        Cuboid::new(
+            Point3::new(min.0, min.1, min.2),
+            Point3::new(max.0, max.1, max.2),
+        )
+    }
+
+    #[test]
+    fn small_example() {
+        let steps = parse_steps(
+            "on x=10..12,y=10..12,z=10..12\n\
+             on x=11..13,y=11..13,z=11..13\n\
+             off x=9..11,y=9..11,z=9..11\n\
+             on x=10..10,y=10..10,z=10..10",
+        )
+        .unwrap();
+        let on = reboot(&steps);
+        assert_eq!(39, on.volume());
+        assert_eq!(39, on.points().collect::<HashSet<_>>().len());
+        assert!(on.contains(Point3::new(10, 10, 10)));
+        assert!(!on.contains(Point3::new(11, 11, 11)));
+        assert!(on.contains(Point3::new(13, 13, 13)));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = CuboidSet::from(cuboid((0, 0, 0), (3, 3, 3)));
+        let b = CuboidSet::from(cuboid((2, 2, 2), (5, 5, 5)));
+        assert_eq!(64 + 64 - 8, a.union(&b).volume());
+        assert_eq!(64 - 8, a.subtract(&b).volume());
+        assert_eq!(8, a.intersect(&b).volume());
+        assert_eq!(0, a.subtract(&a).volume());
+
+        // the pieces stay disjoint, so every cube is in exactly one
+        let union = a.union(&b);
+        let points = union.points().collect::<Vec<_>>();
+        assert_eq!(points.len(), points.iter().collect::<HashSet<_>>().len());
+        for point in cuboid((-1, -1, -1), (6, 6, 6)).points() {
+            assert_eq!(
+                a.contains(point) || b.contains(point),
+                union.contains(point)
+            );
+            assert_eq!(
+                union.iter().filter(|c| c.contains(point)).count(),
+                union.contains(point) as usize
+            );
+        }
+
+        assert_eq!(
+            6,
+            cuboid((0, 0, 0), (2, 2, 2))
+                .difference(&cuboid((1, 1, 1), (1, 1, 1)))
+                .len()
+        );
+        assert_eq!(0, cuboid((1, 0, 0), (0, 0, 0)).volume());
+        assert_eq!(
+            None,
+            cuboid((0, 0, 0), (1, 1, 1)).intersection(&cuboid((2, 0, 0), (3, 1, 1)))
+        );
+    }
+
+    #[test]
+    fn example() {
+        let steps = Day22::parse(&load(2021, 22, Variant::Example(1)).unwrap()).unwrap();
+        assert_answer(2021, 22, Variant::Example(1), 1, Day22::part_1(&steps));
+    }
+
+    #[test]
+    fn example_2() {
+        let steps = Day22::parse(&load(2021, 22, Variant::Example(2)).unwrap()).unwrap();
+        assert_answer(2021, 22, Variant::Example(2), 2, Day22::part_2(&steps));
+    }
+
+    #[test]
+    fn actual() {
+        let steps = Day22::parse(&load(2021, 22, Variant::Real).unwrap()).unwrap();
+        assert_answer(2021, 22, Variant::Real, 1, Day22::part_1(&steps));
+        assert_answer(2021, 22, Variant::Real, 2, Day22::part_2(&steps));
+    }
+}
@@ -0,0 +1,399 @@
+use crate::common::search;
+use crate::error::{source_lines, ParseError, SourceLine};
+use crate::solution::Solution;
+use std::fmt;
+
+const HALLWAY_LENGTH: usize = 11;
+
+/// Hallway positions right outside each room, where amphipods may not stop.
+const ENTRANCES: [usize; 4] = [2, 4, 6, 8];
+
+/// The rows folded out of the diagram in part 2, inserted after the first row.
+const FOLDED_ROWS: [[Amphipod; 4]; 2] = [
+    [
+        Amphipod::Desert,
+        Amphipod::Copper,
+        Amphipod::Bronze,
+        Amphipod::Amber,
+    ],
+    [
+        Amphipod::Desert,
+        Amphipod::Bronze,
+        Amphipod::Amber,
+        Amphipod::Copper,
+    ],
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Amphipod {
+    Amber,
+    Bronze,
+    Copper,
+    Desert,
+}
+
+impl Amphipod {
+    fn from_char(c: char) -> Option<Amphipod> {
+        match c {
+            'A' => Some(Amphipod::Amber),
+            'B' => Some(Amphipod::Bronze),
+            'C' => Some(Amphipod::Copper),
+            'D' => Some(Amphipod::Desert),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Amphipod::Amber => 'A',
+            Amphipod::Bronze => 'B',
+            Amphipod::Copper => 'C',
+            Amphipod::Desert => 'D',
+        }
+    }
+
+    fn energy(self) -> usize {
+        match self {
+            Amphipod::Amber => 1,
+            Amphipod::Bronze => 10,
+            Amphipod::Copper => 100,
+            Amphipod::Desert => 1000,
+        }
+    }
+
+    /// Index of the room this amphipod belongs in.
+    fn room(self) -> usize {
+        self as usize
+    }
+}
+
+/// Positions of all amphipods. Rooms are listed from the slot next to the
+/// hallway down to the back wall.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Burrow {
+    hallway: [Option<Amphipod>; HALLWAY_LENGTH],
+    rooms: [Vec<Option<Amphipod>>; 4],
+}
+
+impl Burrow {
+    fn is_organized(&self) -> bool {
+        self.rooms
+            .iter()
+            .enumerate()
+            .all(|(r, room)| room.iter().all(|slot| slot.is_some_and(|a| a.room() == r)))
+    }
+
+    /// Whether room `r` holds nothing but amphipods that belong there.
+    fn accepts(&self, r: usize) -> bool {
+        self.rooms[r]
+            .iter()
+            .all(|slot| slot.is_none_or(|a| a.room() == r))
+    }
+
+    /// Whether the hallway is empty everywhere between `from` and `to`,
+    /// excluding `from` itself.
+    fn hallway_clear(&self, from: usize, to: usize) -> bool {
+        let range = if from < to {
+            from + 1..to + 1
+        } else {
+            to..from
+        };
+        self.hallway[range].iter().all(Option::is_none)
+    }
+
+    /// Every burrow reachable with a single legal move, with the energy it takes.
+    ///
+    /// Amphipods either leave a room for a hallway spot that is not an
+    /// entrance, or leave the hallway for their own room once it holds no
+    /// strangers. Moving straight from room to room costs the same as
+    /// stopping in the hallway on the way, so it is not generated separately.
+    fn moves(&self) -> Vec<(usize, Burrow)> {
+        let mut moves = vec![];
+
+        for (h, slot) in self.hallway.iter().enumerate() {
+            let amphipod = match slot {
+                Some(a) => *a,
+                None => continue,
+            };
+            let r = amphipod.room();
+            let entrance = ENTRANCES[r];
+            if !self.accepts(r) || !self.hallway_clear(h, entrance) {
+                continue;
+            }
+
+            let depth = self.rooms[r].iter().rposition(Option::is_none).unwrap();
+            let steps = h.abs_diff(entrance) + depth + 1;
+            let mut next = self.clone();
+            next.hallway[h] = None;
+            next.rooms[r][depth] = Some(amphipod);
+            moves.push((steps * amphipod.energy(), next));
+        }
+
+        for (r, room) in self.rooms.iter().enumerate() {
+            if self.accepts(r) {
+                continue;
+            }
+            let depth = room.iter().position(Option::is_some).unwrap();
+            let amphipod = room[depth].unwrap();
+            let entrance = ENTRANCES[r];
+
+            for h in 0..HALLWAY_LENGTH {
+                if ENTRANCES.contains(&h) || !self.hallway_clear(entrance, h) {
+                    continue;
+                }
+                let steps = h.abs_diff(entrance) + depth + 1;
+                let mut next = self.clone();
+                next.rooms[r][depth] = None;
+                next.hallway[h] = Some(amphipod);
+                moves.push((steps * amphipod.energy(), next));
+            }
+        }
+
+        moves
+    }
+
+    /// The burrow with the two rows hidden by the fold inserted.
+    fn unfold(&self) -> Burrow {
+        let mut unfolded = self.clone();
+        for (r, room) in unfolded.rooms.iter_mut().enumerate() {
+            for (i, row) in FOLDED_ROWS.iter().enumerate() {
+                room.insert(1 + i, Some(row[r]));
+            }
+        }
+        unfolded
+    }
+}
+
+impl fmt::Display for Burrow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cell = |slot: &Option<Amphipod>| slot.map_or('.', Amphipod::to_char);
+
+        writeln!(f, "#############")?;
+        writeln!(f, "#{}#", self.hallway.iter().map(cell).collect::<String>())?;
+        for depth in 0..self.rooms[0].len() {
+            let (left, right) = if depth == 0 { ("##", "##") } else { ("  ", "") };
+            write!(f, "{}#", left)?;
+            for room in self.rooms.iter() {
+                write!(f, "{}#", cell(&room[depth]))?;
+            }
+            writeln!(f, "{}", right)?;
+        }
+        write!(f, "  #########")
+    }
+}
+
+fn parse_cell(line: &SourceLine, column: usize) -> Result<Option<Amphipod>, ParseError> {
+    let token = line.text.get(column..column + 1).unwrap_or_default();
+    match token.chars().next() {
+        Some('.') => Ok(None),
+        Some(c) => Amphipod::from_char(c)
+            .map(Some)
+            .ok_or_else(|| line.error(token, "amphipod or '.'")),
+        None => Err(ParseError::missing(line.number, "amphipod or '.'")),
+    }
+}
+
+impl std::str::FromStr for Burrow {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = source_lines(s).collect::<Vec<_>>();
+        let hallway_line = lines
+            .get(1)
+            .ok_or_else(|| ParseError::missing(lines.len() + 1, "hallway"))?;
+
+        // every amphipod with where it was read, to check how many there are
+        let mut found = vec![];
+        let mut cell = |line: &SourceLine<'_>, column: usize| {
+            let slot = parse_cell(line, column)?;
+            if let Some(amphipod) = slot {
+                found.push((line.number, column, amphipod));
+            }
+            Ok::<_, ParseError>(slot)
+        };
+
+        let mut hallway = [None; HALLWAY_LENGTH];
+        for (h, slot) in hallway.iter_mut().enumerate() {
+            *slot = cell(hallway_line, h + 1)?;
+        }
+
+        let mut rooms: [Vec<Option<Amphipod>>; 4] = Default::default();
+        for line in lines.iter().skip(2) {
+            if line.text.trim() == "#########" {
+                break;
+            }
+            for (r, room) in rooms.iter_mut().enumerate() {
+                room.push(cell(line, 3 + 2 * r)?);
+            }
+        }
+
+        let depth = rooms[0].len();
+        if depth == 0 {
+            return Err(ParseError::missing(3, "room row"));
+        }
+
+        // amphipods settle at the back of a room, with no gap under them
+        for (r, room) in rooms.iter().enumerate() {
+            if let Some(top) = room.iter().position(Option::is_some) {
+                if let Some(gap) = room[top..].iter().position(Option::is_none) {
+                    let line = &lines[2 + top + gap];
+                    let column = 3 + 2 * r;
+                    let expected = "amphipod under the one above";
+                    return Err(line.error(&line.text[column..column + 1], expected));
+                }
+            }
+        }
+
+        // each room is only organized once it is full of its own kind
+        let mut counts = [0; 4];
+        for &(number, column, amphipod) in &found {
+            counts[amphipod.room()] += 1;
+            if counts[amphipod.room()] > depth {
+                let line = &lines[number - 1];
+                let expected = format!("{} amphipods of each type", depth);
+                return Err(line.error(&line.text[column..column + 1], expected));
+            }
+        }
+        if let Some(r) = counts.iter().position(|&count| count < depth) {
+            let expected = format!("{} '{}' amphipods", depth, ['A', 'B', 'C', 'D'][r]);
+            return Err(ParseError::missing(depth + 3, expected));
+        }
+        Ok(Burrow { hallway, rooms })
+    }
+}
+
+fn least_energy(start: &Burrow) -> Option<usize> {
+    let moves = |burrow: &Burrow| burrow.moves().into_iter().map(|(cost, next)| (next, cost));
+    search::dijkstra(start.clone(), moves, Burrow::is_organized).map(|path| path.cost)
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Burrow;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse::<Burrow>()
+    }
+
+    // amphipods stopped in the hallway can still block each other for good
+    fn part_1(burrow: &Self::Input) -> Self::Answer1 {
+        least_energy(burrow).map_or("none".to_string(), |e| e.to_string())
+    }
+
+    fn part_2(burrow: &Self::Input) -> Self::Answer2 {
+        least_energy(&burrow.unfold()).map_or("none".to_string(), |e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
+
+    #[test]
+    fn round_trip() {
+        let input = &load(2021, 23, Variant::Example(1)).unwrap();
+        assert_eq!(*input, Day23::parse(input).unwrap().to_string());
+    }
+
+    #[test]
+    fn unfold() {
+        let burrow = Day23::parse(&load(2021, 23, Variant::Example(1)).unwrap()).unwrap();
+        assert_eq!(
+            "#############
+#...........#
+###B#C#B#D###
+  #D#C#B#A#
+  #D#B#A#C#
+  #A#D#C#A#
+  #########",
+            burrow.unfold().to_string()
+        );
+    }
+
+    #[test]
+    fn moves() {
+        let burrow = Day23::parse(
+            "#############
+#.....D.D.A.#
+###.#B#C#.###
+  #A#B#C#.#
+  #########",
+        )
+        .unwrap();
+
+        // the amber amphipod's way home is blocked, and so is the desert
+        // amphipod furthest from its room
+        let home = Day23::parse(
+            "#############
+#.....D...A.#
+###.#B#C#.###
+  #A#B#C#D#
+  #########",
+        )
+        .unwrap();
+        assert_eq!(vec![(3000, home)], burrow.moves());
+    }
+
+    #[test]
+    fn example() {
+        let burrow = Day23::parse(&load(2021, 23, Variant::Example(1)).unwrap()).unwrap();
+        assert_answer(2021, 23, Variant::Example(1), 1, Day23::part_1(&burrow));
+        assert_answer(2021, 23, Variant::Example(1), 2, Day23::part_2(&burrow));
+    }
+
+    #[test]
+    fn malformed() {
+        let input = &load(2021, 23, Variant::Example(1)).unwrap();
+        assert_eq!(
+            ParseError::unexpected(4, 6, "E", "amphipod or '.'"),
+            Day23::parse(&input.replacen("#D#C", "#E#C", 1)).unwrap_err()
+        );
+        assert_eq!(
+            ParseError::missing(2, "hallway"),
+            Day23::parse("#############").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::unexpected(4, 4, ".", "amphipod under the one above"),
+            Day23::parse(
+                "#############
+#.....A.....#
+###A#.#C#D###
+  #.#B#C#D#
+  #########",
+            )
+            .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn counts() {
+        let input = &load(2021, 23, Variant::Example(1)).unwrap();
+        assert_eq!(
+            ParseError::unexpected(4, 10, "A", "2 amphipods of each type"),
+            Day23::parse(&input.replacen("#...........#", "#A..........#", 1)).unwrap_err()
+        );
+        assert_eq!(
+            ParseError::missing(5, "2 'B' amphipods"),
+            Day23::parse(&input.replacen("#B#C", "#.#C", 1)).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn deadlock() {
+        // each amphipod in the hallway waits for the other to get out of its way
+        let burrow = Day23::parse(
+            "#############
+#...D.A.....#
+###.#B#C#.###
+  #A#B#C#D#
+  #########",
+        )
+        .unwrap();
+        assert_eq!("none", Day23::part_1(&burrow));
+    }
+}
@@ -0,0 +1,1079 @@
+use crate::error::{source_lines, ParseError, SourceLine};
+use crate::solution::Solution;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Register {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+impl Register {
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operand {
+    Register(Register),
+    Number(i64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    Inp(Register),
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Div(Register, Operand),
+    Mod(Register, Operand),
+    Eql(Register, Operand),
+}
+
+impl Instruction {
+    /// The register the instruction writes.
+    fn target(self) -> Register {
+        match self {
+            Instruction::Inp(a)
+            | Instruction::Add(a, _)
+            | Instruction::Mul(a, _)
+            | Instruction::Div(a, _)
+            | Instruction::Mod(a, _)
+            | Instruction::Eql(a, _) => a,
+        }
+    }
+
+    /// The second operand, which every instruction but `inp` has.
+    fn operand(self) -> Option<Operand> {
+        match self {
+            Instruction::Inp(_) => None,
+            Instruction::Add(_, b)
+            | Instruction::Mul(_, b)
+            | Instruction::Div(_, b)
+            | Instruction::Mod(_, b)
+            | Instruction::Eql(_, b) => Some(b),
+        }
+    }
+
+    fn with_operand(self, b: Operand) -> Instruction {
+        match self {
+            Instruction::Inp(a) => Instruction::Inp(a),
+            Instruction::Add(a, _) => Instruction::Add(a, b),
+            Instruction::Mul(a, _) => Instruction::Mul(a, b),
+            Instruction::Div(a, _) => Instruction::Div(a, b),
+            Instruction::Mod(a, _) => Instruction::Mod(a, b),
+            Instruction::Eql(a, _) => Instruction::Eql(a, b),
+        }
+    }
+}
+
+fn parse_register(line: &SourceLine, token: &str) -> Result<Register, ParseError> {
+    match token {
+        "w" => Ok(Register::W),
+        "x" => Ok(Register::X),
+        "y" => Ok(Register::Y),
+        "z" => Ok(Register::Z),
+        _ => Err(line.error(token, "register 'w', 'x', 'y' or 'z'")),
+    }
+}
+
+fn parse_operand(line: &SourceLine, token: &str) -> Result<Operand, ParseError> {
+    match parse_register(line, token) {
+        Ok(register) => Ok(Operand::Register(register)),
+        Err(_) => line
+            .parse::<i64>(token, "register or integer")
+            .map(Operand::Number),
+    }
+}
+
+fn parse_instruction(line: &SourceLine) -> Result<Instruction, ParseError> {
+    let mut params = line.text.split_whitespace();
+    let operator = params
+        .next()
+        .ok_or_else(|| ParseError::missing(line.number, "instruction"))?;
+    let mut operand = || {
+        params
+            .next()
+            .ok_or_else(|| ParseError::missing(line.number, "operand"))
+    };
+
+    let target = parse_register(line, operand()?)?;
+    let instruction = match operator {
+        "inp" => Instruction::Inp(target),
+        "add" => Instruction::Add(target, parse_operand(line, operand()?)?),
+        "mul" => Instruction::Mul(target, parse_operand(line, operand()?)?),
+        "div" => Instruction::Div(target, parse_operand(line, operand()?)?),
+        "mod" => Instruction::Mod(target, parse_operand(line, operand()?)?),
+        "eql" => Instruction::Eql(target, parse_operand(line, operand()?)?),
+        _ => return Err(line.error(operator, "'inp', 'add', 'mul', 'div', 'mod' or 'eql'")),
+    };
+    if let Some(extra) = params.next() {
+        return Err(line.error(extra, "end of instruction"));
+    }
+
+    Ok(instruction)
+}
+
+/// Why the ALU stopped before the end of a program, with the index of the
+/// instruction it stopped at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AluError {
+    /// An `inp` ran out of input.
+    InputExhausted { at: usize },
+    /// A `div` by zero.
+    DivisionByZero { at: usize },
+    /// A `mod` of a negative number or by a number that isn't positive.
+    InvalidModulo { at: usize, a: i64, b: i64 },
+}
+
+impl fmt::Display for AluError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AluError::InputExhausted { at } => write!(f, "instruction {}: out of input", at),
+            AluError::DivisionByZero { at } => write!(f, "instruction {}: division by zero", at),
+            AluError::InvalidModulo { at, a, b } => {
+                write!(f, "instruction {}: invalid modulo {} % {}", at, a, b)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AluError {}
+
+/// The result of the `at`th instruction of a program, which isn't an `inp`,
+/// on the values `a` of its target and `b` of its operand. The puzzle
+/// doesn't say what happens on overflow, so `add`, `mul` and `div` wrap
+/// around like the 64-bit registers of real hardware.
+fn arithmetic(at: usize, instruction: Instruction, a: i64, b: i64) -> Result<i64, AluError> {
+    match instruction {
+        Instruction::Inp(_) => unreachable!("inp has no operand"),
+        Instruction::Add(..) => Ok(a.wrapping_add(b)),
+        Instruction::Mul(..) => Ok(a.wrapping_mul(b)),
+        Instruction::Div(..) if b == 0 => Err(AluError::DivisionByZero { at }),
+        Instruction::Div(..) => Ok(a.wrapping_div(b)),
+        Instruction::Mod(..) if a < 0 || b <= 0 => Err(AluError::InvalidModulo { at, a, b }),
+        Instruction::Mod(..) => Ok(a % b),
+        Instruction::Eql(..) => Ok((a == b) as i64),
+    }
+}
+
+/// The arithmetic logic unit of the submarine: four registers that
+/// instructions read and write, and a stream of input for `inp`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Alu {
+    pub registers: [i64; 4],
+}
+
+impl Alu {
+    pub fn new() -> Alu {
+        Alu::default()
+    }
+
+    pub fn get(&self, register: Register) -> i64 {
+        self.registers[register.index()]
+    }
+
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(register) => self.get(register),
+            Operand::Number(number) => number,
+        }
+    }
+
+    /// Executes a single instruction, which is the `at`th of its program.
+    pub fn step(
+        &mut self,
+        at: usize,
+        instruction: Instruction,
+        input: &mut impl Iterator<Item = i64>,
+    ) -> Result<(), AluError> {
+        let target = instruction.target();
+        self.registers[target.index()] = match instruction.operand() {
+            None => input.next().ok_or(AluError::InputExhausted { at })?,
+            Some(b) => arithmetic(at, instruction, self.get(target), self.value(b))?,
+        };
+        Ok(())
+    }
+
+    /// Runs every instruction of `program`, reading the value of each `inp`
+    /// from `input`.
+    pub fn run(
+        &mut self,
+        program: &[Instruction],
+        input: impl IntoIterator<Item = i64>,
+    ) -> Result<(), AluError> {
+        let mut input = input.into_iter();
+        for (at, &instruction) in program.iter().enumerate() {
+            self.step(at, instruction, &mut input)?;
+        }
+        Ok(())
+    }
+}
+
+/// One step of a compiled program. Every step that can crash keeps the
+/// index of the instruction it came from, to report the same error as the
+/// ALU.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Inp {
+        at: usize,
+        target: Register,
+    },
+    Set {
+        target: Register,
+        value: i64,
+    },
+    Copy {
+        target: Register,
+        source: Register,
+    },
+    /// An instruction whose register operand, if any, wasn't known.
+    Apply {
+        at: usize,
+        instruction: Instruction,
+    },
+}
+
+impl Op {
+    fn target(self) -> Register {
+        match self {
+            Op::Inp { target, .. } | Op::Set { target, .. } | Op::Copy { target, .. } => target,
+            Op::Apply { instruction, .. } => instruction.target(),
+        }
+    }
+
+    /// Whether the op reads `register`.
+    fn reads(self, register: Register) -> bool {
+        match self {
+            Op::Inp { .. } | Op::Set { .. } => false,
+            Op::Copy { source, .. } => source == register,
+            Op::Apply { instruction, .. } => {
+                instruction.target() == register
+                    || instruction.operand() == Some(Operand::Register(register))
+            }
+        }
+    }
+
+    /// Whether the op has an effect besides its target: consuming input or
+    /// possibly crashing.
+    fn has_side_effects(self) -> bool {
+        match self {
+            Op::Inp { .. } => true,
+            Op::Set { .. } | Op::Copy { .. } => false,
+            Op::Apply { instruction, .. } => match instruction {
+                Instruction::Div(_, b) => !matches!(b, Operand::Number(n) if n != 0),
+                Instruction::Mod(..) => true,
+                _ => false,
+            },
+        }
+    }
+}
+
+/// An ALU program compiled to a shorter list of ops: instructions on values
+/// known while compiling are folded into constants, instructions that can't
+/// change anything are dropped, and so are writes that are overwritten
+/// before they're read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Compiled {
+    ops: Vec<Op>,
+}
+
+/// Compiles `program` to run on registers that start out with the values
+/// that are `Some` in `known`.
+pub fn compile(program: &[Instruction], known: [Option<i64>; 4]) -> Compiled {
+    let mut known = known;
+    let mut ops = Vec::new();
+    for (at, &instruction) in program.iter().enumerate() {
+        let target = instruction.target();
+        let b = match instruction.operand() {
+            None => {
+                ops.push(Op::Inp { at, target });
+                known[target.index()] = None;
+                continue;
+            }
+            Some(Operand::Register(r)) => {
+                known[r.index()].map_or(Operand::Register(r), Operand::Number)
+            }
+            Some(number) => number,
+        };
+        let instruction = instruction.with_operand(b);
+        let value = match b {
+            Operand::Number(n) => Some(n),
+            Operand::Register(_) => None,
+        };
+
+        let (op, result) = match (instruction, known[target.index()], value) {
+            (_, Some(a), Some(b)) => match arithmetic(at, instruction, a, b) {
+                Ok(result) if Some(result) == known[target.index()] => continue,
+                Ok(result) => (
+                    Op::Set {
+                        target,
+                        value: result,
+                    },
+                    Some(result),
+                ),
+                // left for the compiled program to crash on
+                Err(_) => (Op::Apply { at, instruction }, None),
+            },
+            (Instruction::Add(..), _, Some(0))
+            | (Instruction::Mul(..), _, Some(1))
+            | (Instruction::Div(..), _, Some(1))
+            | (Instruction::Mul(..), Some(0), _) => continue,
+            (Instruction::Mul(..), _, Some(0)) => (Op::Set { target, value: 0 }, Some(0)),
+            (Instruction::Add(_, Operand::Register(source)), Some(0), _) => {
+                (Op::Copy { target, source }, None)
+            }
+            _ => (Op::Apply { at, instruction }, None),
+        };
+        ops.push(op);
+        known[target.index()] = result;
+    }
+
+    // every register is read at the end, walking back from there drops the
+    // writes that nothing reads
+    let mut live = [true; 4];
+    let mut kept = Vec::new();
+    for op in ops.into_iter().rev() {
+        let target = op.target().index();
+        if !live[target] && !op.has_side_effects() {
+            continue;
+        }
+        live[target] = false;
+        for register in [Register::W, Register::X, Register::Y, Register::Z] {
+            if op.reads(register) {
+                live[register.index()] = true;
+            }
+        }
+        kept.push(op);
+    }
+    kept.reverse();
+
+    Compiled { ops: kept }
+}
+
+impl Compiled {
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Runs the program on `registers`, which must hold the values the
+    /// program was compiled for, reading the value of each `inp` from `input`.
+    pub fn run(
+        &self,
+        registers: &mut [i64; 4],
+        input: impl IntoIterator<Item = i64>,
+    ) -> Result<(), AluError> {
+        let mut input = input.into_iter();
+        for &op in &self.ops {
+            match op {
+                Op::Inp { at, target } => {
+                    registers[target.index()] =
+                        input.next().ok_or(AluError::InputExhausted { at })?
+                }
+                Op::Set { target, value } => registers[target.index()] = value,
+                Op::Copy { target, source } => {
+                    registers[target.index()] = registers[source.index()]
+                }
+                Op::Apply { at, instruction } => {
+                    let target = instruction.target().index();
+                    let b = match instruction.operand() {
+                        Some(Operand::Register(r)) => registers[r.index()],
+                        Some(Operand::Number(n)) => n,
+                        None => unreachable!("inp is compiled to Op::Inp"),
+                    };
+                    registers[target] = arithmetic(at, instruction, registers[target], b)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Splits `program` before every `inp`, one block per digit of the model
+/// number.
+fn digit_blocks(program: &[Instruction]) -> Vec<&[Instruction]> {
+    let mut blocks = Vec::new();
+    let mut rest = program;
+    while !rest.is_empty() {
+        let end = rest[1..]
+            .iter()
+            .position(|i| matches!(i, Instruction::Inp(_)))
+            .map_or(rest.len(), |p| p + 1);
+        let (block, tail) = rest.split_at(end);
+        blocks.push(block);
+        rest = tail;
+    }
+    blocks
+}
+
+type Cache = HashMap<(i64, usize), Option<String>>;
+
+/// The first model number in `range` order for the digits from `block` on,
+/// when `z` holds what the blocks before left in it.
+fn find_modelnum(
+    memo: &mut Cache,
+    blocks: &[Compiled],
+    block: usize,
+    z: i64,
+    range: &[i64; 9],
+) -> Option<String> {
+    if block == blocks.len() {
+        return (z == 0).then(String::new);
+    }
+    if let Some(answer) = memo.get(&(z, block)) {
+        return answer.clone();
+    }
+
+    let mut answer = None;
+    for &digit in range {
+        let mut registers = [0, 0, 0, z];
+        // a digit that crashes the ALU can't be part of a valid number
+        if blocks[block].run(&mut registers, Some(digit)).is_err() {
+            continue;
+        }
+        let next_z = registers[Register::Z.index()];
+        if let Some(rest) = find_modelnum(memo, blocks, block + 1, next_z, range) {
+            answer = Some(format!("{}{}", digit, rest));
+            break;
+        }
+    }
+
+    memo.insert((z, block), answer.clone());
+    answer
+}
+
+/// Searches for the largest or smallest model number digit by digit, or
+/// `None` if no number is valid. Only `z` is carried from one block to the
+/// next, which `Day24::parse` checks for.
+fn model_number(program: &[Instruction], biggest: bool) -> Option<String> {
+    let range = if biggest {
+        [9, 8, 7, 6, 5, 4, 3, 2, 1]
+    } else {
+        [1, 2, 3, 4, 5, 6, 7, 8, 9]
+    };
+    if !program.iter().any(|i| matches!(i, Instruction::Inp(_))) {
+        return None;
+    }
+    let blocks = digit_blocks(program)
+        .into_iter()
+        .map(|block| compile(block, [Some(0), Some(0), Some(0), None]))
+        .collect::<Vec<_>>();
+
+    find_modelnum(&mut Cache::new(), &blocks, 0, 0, &range)
+}
+
+/// The first instruction that reads `w`, `x` or `y` as left by an earlier
+/// digit's block, before writing it itself. MONAD only passes `z` on.
+fn carried_read(program: &[Instruction]) -> Option<usize> {
+    // everything starts out 0, which the first block may rely on
+    let mut written = [true; 4];
+    let mut inputs = 0;
+    for (at, &instruction) in program.iter().enumerate() {
+        let target = instruction.target();
+        let mut reads = vec![];
+        match instruction {
+            Instruction::Inp(_) => {
+                inputs += 1;
+                if inputs > 1 {
+                    written = [false, false, false, true];
+                }
+            }
+            // the result doesn't depend on the old value
+            Instruction::Mul(_, Operand::Number(0)) => {}
+            _ => reads.push(target),
+        }
+        if let Some(Operand::Register(r)) = instruction.operand() {
+            reads.push(r);
+        }
+        if reads.iter().any(|r| !written[r.index()]) {
+            return Some(at);
+        }
+        written[target.index()] = true;
+    }
+    None
+}
+
+/// A symbolic value: a digit of the model number plus a constant, or just a
+/// constant when `digit` is `None`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Term {
+    digit: Option<usize>,
+    offset: i64,
+}
+
+impl Term {
+    fn constant(value: i64) -> Term {
+        Term {
+            digit: None,
+            offset: value,
+        }
+    }
+
+    fn is(self, value: i64) -> bool {
+        self == Term::constant(value)
+    }
+
+    /// Whether the term always lies in `0..26`, so it can be one base 26 digit
+    /// of a larger number.
+    fn fits_base(self) -> bool {
+        match self.digit {
+            Some(_) => (-1..BASE - 9).contains(&self.offset),
+            None => (0..BASE).contains(&self.offset),
+        }
+    }
+}
+
+const BASE: i64 = 26;
+
+/// What a register holds during the analysis: a single term, or a stack of
+/// at least two terms that each fit in base 26, the last one the least
+/// significant. MONAD keeps such a stack in `z`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Value {
+    Term(Term),
+    Stack(Vec<Term>),
+}
+
+impl Value {
+    fn stack(mut terms: Vec<Term>) -> Value {
+        match terms.len() {
+            0 => Value::Term(Term::constant(0)),
+            1 => Value::Term(terms[0]),
+            _ => {
+                if terms[0].is(0) {
+                    terms.remove(0);
+                    return Value::stack(terms);
+                }
+                Value::Stack(terms)
+            }
+        }
+    }
+
+    fn terms(&self) -> Option<Vec<Term>> {
+        match self {
+            Value::Term(t) if t.is(0) => Some(Vec::new()),
+            Value::Term(t) if t.fits_base() => Some(vec![*t]),
+            Value::Term(_) => None,
+            Value::Stack(terms) => Some(terms.clone()),
+        }
+    }
+}
+
+/// Two digits of a valid model number must satisfy
+/// `digit later = digit earlier + difference`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Constraint {
+    pub earlier: usize,
+    pub later: usize,
+    pub difference: i64,
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.difference < 0 { '-' } else { '+' };
+        write!(
+            f,
+            "digit {} = digit {} {} {}",
+            self.later + 1,
+            self.earlier + 1,
+            sign,
+            self.difference.abs()
+        )
+    }
+}
+
+/// The digit constraints found in a program, and the model numbers they
+/// allow, each checked by running the program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Analysis {
+    pub digits: usize,
+    pub constraints: Vec<Constraint>,
+    pub largest: String,
+    pub smallest: String,
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} digits, with", self.digits)?;
+        for constraint in &self.constraints {
+            writeln!(f, "  {}", constraint)?;
+        }
+        writeln!(f, "largest: {}", self.largest)?;
+        write!(f, "smallest: {}", self.smallest)
+    }
+}
+
+/// Applies one instruction to symbolic values, recording the constraint the
+/// digits must meet whenever an `eql` could go either way. Programs with
+/// other shapes than MONAD's push/pop blocks give `None`.
+fn evaluate(
+    instruction: Instruction,
+    registers: &mut [Value; 4],
+    digits: &mut usize,
+    constraints: &mut Vec<Constraint>,
+) -> Option<()> {
+    let (a, b) = match instruction {
+        Instruction::Inp(a) => {
+            registers[a.index()] = Value::Term(Term {
+                digit: Some(*digits),
+                offset: 0,
+            });
+            *digits += 1;
+            return Some(());
+        }
+        Instruction::Add(a, b)
+        | Instruction::Mul(a, b)
+        | Instruction::Div(a, b)
+        | Instruction::Mod(a, b)
+        | Instruction::Eql(a, b) => (a, b),
+    };
+    let left = registers[a.index()].clone();
+    let zero = || Value::Term(Term::constant(0));
+    let right = match b {
+        Operand::Register(r) => registers[r.index()].clone(),
+        Operand::Number(n) => Value::Term(Term::constant(n)),
+    };
+    let right = match right {
+        Value::Term(term) => term,
+        // a stack can only be copied, to work on its top term
+        stack if matches!(instruction, Instruction::Add(..)) && left == zero() => {
+            registers[a.index()] = stack;
+            return Some(());
+        }
+        Value::Stack(_) => return None,
+    };
+
+    let result = match (instruction, left) {
+        (Instruction::Add(..), Value::Term(l)) if l.digit.is_none() || right.digit.is_none() => {
+            Value::Term(Term {
+                digit: l.digit.or(right.digit),
+                offset: l.offset.wrapping_add(right.offset),
+            })
+        }
+        (Instruction::Add(..), Value::Stack(mut terms)) => {
+            let top = terms.last_mut()?;
+            if top.digit.is_some() && right.digit.is_some() {
+                return None;
+            }
+            *top = Term {
+                digit: top.digit.or(right.digit),
+                offset: top.offset.wrapping_add(right.offset),
+            };
+            if !top.fits_base() {
+                return None;
+            }
+            Value::stack(terms)
+        }
+        (Instruction::Mul(..), _) if right.is(0) => Value::Term(Term::constant(0)),
+        (Instruction::Mul(..), left) if right.is(1) => left,
+        (Instruction::Mul(..), Value::Term(l)) if l.digit.is_none() && right.digit.is_none() => {
+            Value::Term(Term::constant(l.offset.wrapping_mul(right.offset)))
+        }
+        (Instruction::Mul(..), left) if right.is(BASE) => {
+            let mut terms = left.terms()?;
+            terms.push(Term::constant(0));
+            Value::stack(terms)
+        }
+        (Instruction::Div(..), left) if right.is(1) => left,
+        (Instruction::Div(..), left) if right.is(BASE) => {
+            let mut terms = left.terms()?;
+            terms.pop();
+            Value::stack(terms)
+        }
+        (Instruction::Mod(..), left) if right.is(BASE) => {
+            Value::stack(left.terms()?.last().copied().into_iter().collect())
+        }
+        (Instruction::Eql(..), Value::Term(l)) => match (l.digit, right.digit) {
+            (None, None) => Value::Term(Term::constant((l.offset == right.offset) as i64)),
+            (Some(i), Some(j)) if i == j => Value::Term(Term::constant((l == right) as i64)),
+            (Some(i), Some(j)) => {
+                // digits are 1 to 9, so they can only differ by up to 8
+                let difference = l.offset.wrapping_sub(right.offset);
+                if difference.unsigned_abs() > 8 {
+                    Value::Term(Term::constant(0))
+                } else {
+                    // the choice that keeps z from growing, as it has to
+                    // shrink back to 0
+                    let (earlier, later, difference) = if i < j {
+                        (i, j, difference)
+                    } else {
+                        (j, i, -difference)
+                    };
+                    constraints.push(Constraint {
+                        earlier,
+                        later,
+                        difference,
+                    });
+                    Value::Term(Term::constant(1))
+                }
+            }
+            _ => {
+                let (term, constant) = if l.digit.is_some() {
+                    (l, right)
+                } else {
+                    (right, l)
+                };
+                if (1..=9).contains(&constant.offset.wrapping_sub(term.offset)) {
+                    return None;
+                }
+                Value::Term(Term::constant(0))
+            }
+        },
+        _ => return None,
+    };
+    registers[a.index()] = result;
+    Some(())
+}
+
+/// Works out which pairs of digits have to be related for `program` to leave
+/// `z` at 0, and from that the largest and smallest valid model numbers.
+fn analyze(program: &[Instruction]) -> Option<Analysis> {
+    let zero = Value::Term(Term::constant(0));
+    let mut registers = [zero.clone(), zero.clone(), zero.clone(), zero.clone()];
+    let mut digits = 0;
+    let mut constraints = Vec::new();
+    for &instruction in program {
+        evaluate(instruction, &mut registers, &mut digits, &mut constraints)?;
+    }
+    if registers[Register::Z.index()] != zero {
+        return None;
+    }
+
+    let number = |pick: fn(i64) -> (i64, i64), free: i64| {
+        let mut number = vec![free; digits];
+        for c in &constraints {
+            let (earlier, later) = pick(c.difference);
+            number[c.earlier] = earlier;
+            number[c.later] = later;
+        }
+        // the constraints were assumed, so only trust numbers that check out
+        let mut alu = Alu::new();
+        alu.run(program, number.iter().copied()).ok()?;
+        if alu.get(Register::Z) != 0 {
+            return None;
+        }
+        Some(number.iter().map(|d| d.to_string()).collect::<String>())
+    };
+    let largest = number(|k| if k < 0 { (9, 9 + k) } else { (9 - k, 9) }, 9)?;
+    let smallest = number(|k| if k < 0 { (1 - k, 1) } else { (1, 1 + k) }, 1)?;
+
+    Some(Analysis {
+        digits,
+        constraints,
+        largest,
+        smallest,
+    })
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Vec<Instruction>;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let program = source_lines(input)
+            .map(|line| parse_instruction(&line))
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Some(at) = carried_read(&program) {
+            let line = source_lines(input).nth(at).unwrap();
+            return Err(line.error(line.text, "no reads of w, x or y left by an earlier digit"));
+        }
+        Ok(program)
+    }
+
+    fn part_1(program: &Self::Input) -> Self::Answer1 {
+        match analyze(program) {
+            Some(analysis) => analysis.largest,
+            None => model_number(program, true).unwrap_or_else(|| "none".to_string()),
+        }
+    }
+
+    fn part_2(program: &Self::Input) -> Self::Answer2 {
+        match analyze(program) {
+            Some(analysis) => analysis.smallest,
+            None => model_number(program, false).unwrap_or_else(|| "none".to_string()),
+        }
+    }
+
+    fn render(program: &Self::Input, _part: u32) -> Option<String> {
+        analyze(program).map(|analysis| analysis.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            vec![
+                Instruction::Inp(Register::W),
+                Instruction::Add(Register::Z, Operand::Register(Register::W)),
+                Instruction::Mod(Register::Z, Operand::Number(-26)),
+            ],
+            Day24::parse("inp w\nadd z w\nmod z -26").unwrap()
+        );
+        assert_eq!(
+            ParseError::unexpected(2, 1, "sub", "'inp', 'add', 'mul', 'div', 'mod' or 'eql'"),
+            Day24::parse("inp w\nsub z w").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::unexpected(1, 7, "q", "register or integer"),
+            Day24::parse("add z q").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::missing(1, "operand"),
+            Day24::parse("mul x").unwrap_err()
+        );
+    }
+
+    fn run(program: &str, input: &[i64]) -> Result<[i64; 4], AluError> {
+        let mut alu = Alu::new();
+        alu.run(&Day24::parse(program).unwrap(), input.iter().copied())?;
+        Ok(alu.registers)
+    }
+
+    #[test]
+    fn examples() {
+        assert_eq!(Ok(-7), run("inp x\nmul x -1", &[7]).map(|r| r[1]));
+
+        let three_times = "inp z\ninp x\nmul z 3\neql z x";
+        assert_eq!(Ok(1), run(three_times, &[2, 6]).map(|r| r[3]));
+        assert_eq!(Ok(0), run(three_times, &[2, 7]).map(|r| r[3]));
+
+        // stores the lowest four bits of the input in w, x, y and z
+        let binary = "inp w\nadd z w\nmod z 2\ndiv w 2\nadd y w\nmod y 2\n\
+                      div w 2\nadd x w\nmod x 2\ndiv w 2\nmod w 2";
+        assert_eq!(Ok([1, 1, 0, 1]), run(binary, &[13]));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Err(AluError::InputExhausted { at: 1 }),
+            run("inp w\ninp x", &[1])
+        );
+        assert_eq!(
+            Err(AluError::DivisionByZero { at: 1 }),
+            run("inp w\ndiv w x", &[1])
+        );
+        assert_eq!(
+            Err(AluError::InvalidModulo { at: 1, a: -3, b: 2 }),
+            run("inp w\nmod w 2", &[-3])
+        );
+        assert_eq!(
+            Err(AluError::InvalidModulo { at: 1, a: 3, b: 0 }),
+            run("inp w\nmod w x", &[3])
+        );
+        assert_eq!(
+            "instruction 1: invalid modulo 3 % 0",
+            run("inp w\nmod w x", &[3]).unwrap_err().to_string()
+        );
+        // integer division rounds toward zero
+        assert_eq!(Ok(-2), run("inp w\ndiv w 3", &[-7]).map(|r| r[0]));
+        // and overflow wraps around instead of stopping the ALU
+        let overflow = "add x -9223372036854775807\nadd x -1\ndiv x -1\nadd y x\nadd y -1";
+        assert_eq!(Ok([0, i64::MIN, i64::MAX, 0]), run(overflow, &[]));
+        assert_eq!(
+            Ok(-2),
+            run("inp w\nmul w 9223372036854775807", &[2]).map(|r| r[0])
+        );
+    }
+
+    #[test]
+    fn unsupported() {
+        // squaring the input isn't a push or a pop
+        let program = Day24::parse("inp w\nmul w w\nadd z w\nadd z -4").unwrap();
+        assert_eq!(None, analyze(&program));
+        assert_eq!(Some("2".to_string()), model_number(&program, true));
+
+        // no digit brings z back to 0
+        let program = Day24::parse("inp w\nadd z 1").unwrap();
+        assert_eq!("none", Day24::part_1(&program));
+        assert_eq!("none", Day24::part_2(&program));
+
+        // the search only carries z from one digit to the next
+        assert_eq!(
+            ParseError::unexpected(
+                3,
+                1,
+                "add z w",
+                "no reads of w, x or y left by an earlier digit"
+            ),
+            Day24::parse("inp w\ninp x\nadd z w").unwrap_err()
+        );
+        assert!(Day24::parse("inp w\ninp w\nmul x 0\nadd x w\nadd z x").is_ok());
+    }
+
+    /// A random program of `len` instructions from a linear congruential
+    /// generator seeded with `seed`, reading at most `inputs` values. Numbers
+    /// are mostly small, so zeros are likely enough to exercise the folds and
+    /// the crashes, with the odd extreme one to make them overflow.
+    fn random_program(seed: u64, len: usize, inputs: usize) -> Vec<Instruction> {
+        let mut state = seed;
+        let mut next = |n: u64| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) % n
+        };
+        let registers = [Register::W, Register::X, Register::Y, Register::Z];
+        let mut read = 0;
+        (0..len)
+            .map(|_| {
+                let a = registers[next(4) as usize];
+                let b = match next(3) {
+                    0 => Operand::Register(registers[next(4) as usize]),
+                    1 if next(8) == 0 => Operand::Number([i64::MIN, i64::MAX][next(2) as usize]),
+                    _ => Operand::Number(next(7) as i64 - 3),
+                };
+                match next(6) {
+                    0 if read < inputs => {
+                        read += 1;
+                        Instruction::Inp(a)
+                    }
+                    0 | 1 => Instruction::Add(a, b),
+                    2 => Instruction::Mul(a, b),
+                    3 => Instruction::Div(a, b),
+                    4 => Instruction::Mod(a, b),
+                    _ => Instruction::Eql(a, b),
+                }
+            })
+            .collect()
+    }
+
+    /// Runs `program` on both the ALU and its compiled form from `registers`,
+    /// asserting they agree on the registers or the crash.
+    fn differential(program: &[Instruction], registers: [i64; 4], input: &[i64]) {
+        let mut alu = Alu { registers };
+        let expected = alu
+            .run(program, input.iter().copied())
+            .map(|_| alu.registers);
+
+        let known = [Some(registers[0]), Some(registers[1]), None, None];
+        let compiled = compile(program, known);
+        let mut actual = registers;
+        let actual = compiled
+            .run(&mut actual, input.iter().copied())
+            .map(|_| actual);
+        assert_eq!(
+            expected, actual,
+            "{:?} from {:?} compiled to {:?}",
+            program, registers, compiled
+        );
+    }
+
+    #[test]
+    fn compiled() {
+        let program = Day24::parse("inp x\nmul x 0\nadd x 3\nmul y 0\nadd y x").unwrap();
+        let compiled = compile(&program, [Some(0); 4]);
+        // the input is overwritten unread, and y is folded to 3 along with x
+        assert_eq!(
+            vec![
+                Op::Inp {
+                    at: 0,
+                    target: Register::X
+                },
+                Op::Set {
+                    target: Register::X,
+                    value: 3
+                },
+                Op::Set {
+                    target: Register::Y,
+                    value: 3
+                },
+            ],
+            compiled.ops
+        );
+
+        // folding must keep the crash, and report the same instruction
+        let program = Day24::parse("add x 5\nmul y x\ndiv x y\nmod y x").unwrap();
+        let mut registers = [0; 4];
+        assert_eq!(
+            Err(AluError::DivisionByZero { at: 2 }),
+            compile(&program, [Some(0); 4]).run(&mut registers, None)
+        );
+    }
+
+    #[test]
+    fn differential_random() {
+        for seed in 0..2000 {
+            let program = random_program(seed, 20, 3);
+            for &registers in &[[0; 4], [1, -2, 3, 7], [-5, 4, 0, 2]] {
+                differential(&program, registers, &[5, -1, 9]);
+                differential(&program, registers, &[2]);
+            }
+        }
+    }
+
+    #[test]
+    fn differential_actual() {
+        let program = Day24::parse(&load(2021, 24, Variant::Real).unwrap()).unwrap();
+        for seed in 24..124 {
+            let digits = random_program(seed, 14, 0)
+                .iter()
+                .map(|i| match i.operand() {
+                    Some(Operand::Number(n)) => n.rem_euclid(9) + 1,
+                    _ => 5,
+                })
+                .collect::<Vec<_>>();
+            differential(&program, [0; 4], &digits);
+            for (block, z) in digit_blocks(&program).into_iter().zip(0..) {
+                differential(block, [0, 0, 0, z * 1000 + digits[0]], &digits[1..2]);
+            }
+        }
+
+        // the checks of each block fold into far fewer ops
+        let blocks = digit_blocks(&program);
+        let compiled = blocks
+            .iter()
+            .map(|block| compile(block, [Some(0), Some(0), Some(0), None]).len())
+            .sum::<usize>();
+        assert!(compiled <= program.len() * 5 / 6, "{} ops", compiled);
+    }
+
+    #[test]
+    fn actual() {
+        let program = Day24::parse(&load(2021, 24, Variant::Real).unwrap()).unwrap();
+        assert_eq!(14, digit_blocks(&program).len());
+        let largest = model_number(&program, true).unwrap();
+        assert_answer(2021, 24, Variant::Real, 1, largest);
+    }
+
+    #[test]
+    fn actual_analysis() {
+        let program = Day24::parse(&load(2021, 24, Variant::Real).unwrap()).unwrap();
+        let analysis = analyze(&program).unwrap();
+        assert_eq!(14, analysis.digits);
+        assert_eq!(7, analysis.constraints.len());
+        assert_eq!(
+            Some("digit 4 = digit 3 - 8".to_string()),
+            analysis.constraints.first().map(|c| c.to_string())
+        );
+        assert_eq!(
+            Some("largest: 99911993949684"),
+            analysis.to_string().lines().nth(8)
+        );
+        assert_eq!(Some(analysis.to_string()), Day24::render(&program, 2));
+
+        assert_answer(2021, 24, Variant::Real, 1, Day24::part_1(&program));
+    }
+
+    #[test]
+    fn actual_smallest() {
+        let program = Day24::parse(&load(2021, 24, Variant::Real).unwrap()).unwrap();
+        assert_answer(2021, 24, Variant::Real, 2, Day24::part_2(&program));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::error::{source_lines, ParseError};
+use crate::solution::Solution;
 struct Data {
     readings: Vec<i64>,
     reading_count: i64,
@@ -85,25 +87,80 @@ fn get_data(input: &str) -> Data {
     data
 }
 
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = String;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let width = match input.lines().next() {
+            Some(first) if !first.is_empty() => first.len(),
+            _ => return Err(ParseError::missing(1, "binary number")),
+        };
+
+        for line in source_lines(input) {
+            if let Some((i, c)) = line
+                .text
+                .char_indices()
+                .find(|&(_, c)| c != '0' && c != '1')
+            {
+                return Err(line.error(&line.text[i..i + c.len_utf8()], "'0' or '1'"));
+            }
+            if line.text.len() != width {
+                return Err(line.error(line.text, format!("{} binary digits", width)));
+            }
+        }
+
+        Ok(input.to_string())
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Answer1 {
+        get_data(input).power_consumption()
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Answer2 {
+        get_data(input).life_support(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
+
     #[test]
     fn day_3() {
-        let file = include_str!("../input/day_3.txt");
+        let file = &load(2021, 3, Variant::Real).unwrap();
 
         let data = super::get_data(file);
 
-        assert_eq!(2261546, data.power_consumption());
-        assert_eq!(6775520, data.life_support(file));
+        assert_answer(2021, 3, Variant::Real, 1, data.power_consumption());
+        assert_answer(2021, 3, Variant::Real, 2, data.life_support(file));
     }
 
     #[test]
     fn day_3_example() {
-        let file = include_str!("../input/day_3_example.txt");
+        let file = &load(2021, 3, Variant::Example(1)).unwrap();
 
         let data = super::get_data(file);
 
         let result = data.life_support(file);
-        assert_eq!(230, result);
+        assert_answer(2021, 3, Variant::Example(1), 2, result);
+    }
+
+    #[test]
+    fn malformed() {
+        use super::{Day3, ParseError, Solution};
+
+        assert_eq!(
+            ParseError::unexpected(2, 2, "é", "'0' or '1'"),
+            Day3::parse("001\n0é1").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::unexpected(2, 1, "0101", "3 binary digits"),
+            Day3::parse("001\n0101").unwrap_err()
+        );
     }
 }
@@ -1,7 +1,7 @@
-use std::slice::Chunks;
-
+use crate::error::{source_lines, ParseError, SourceLine};
+use crate::solution::Solution;
 const BOARDSIZE: usize = 5;
-struct Data {
+pub struct Data {
     drawn_numbers: Vec<i32>,
     boards: Vec<Board>,
 }
@@ -64,19 +64,26 @@ impl Data {
 }
 
 impl std::str::FromStr for Data {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines: Vec<&str> = s.lines().collect();
-
-        let mut boards = lines[2..].chunks(BOARDSIZE + 1);
+        let lines: Vec<SourceLine> = source_lines(s).collect();
+        let drawn = lines
+            .first()
+            .ok_or_else(|| ParseError::missing(1, "drawn numbers"))?;
 
         Ok(Data {
-            drawn_numbers: lines[0]
+            drawn_numbers: drawn
+                .text
                 .split(',')
-                .map(|s| i32::from_str(s).unwrap())
-                .collect(),
-            boards: Board::from_chunks(&mut boards),
+                .map(|n| drawn.parse::<i32>(n, "drawn number"))
+                .collect::<Result<_, _>>()?,
+            boards: lines
+                .get(2..)
+                .unwrap_or_default()
+                .chunks(BOARDSIZE + 1)
+                .map(Board::from_lines)
+                .collect::<Result<_, _>>()?,
         })
     }
 }
@@ -97,8 +104,8 @@ impl Board {
         // check columns
         for c in 0..BOARDSIZE {
             let mut column: [i32; BOARDSIZE] = [0; BOARDSIZE];
-            for r in 0..BOARDSIZE {
-                column[r] = self.numbers[r][c];
+            for (r, value) in column.iter_mut().enumerate() {
+                *value = self.numbers[r][c];
             }
 
             if Board::is_winning_line(drawn, &column) {
@@ -119,48 +126,89 @@ impl Board {
     fn is_winning_line(drawn: &[i32], line: &[i32]) -> bool {
         line.iter().all(|n| drawn.contains(n))
     }
-    fn from_lines(lines: &[&str]) -> Board {
-        use std::str::FromStr;
-        Board {
-            numbers: lines[0..BOARDSIZE]
-                .iter()
-                .map(|r| {
-                    r.split_whitespace()
-                        .map(|s| i32::from_str(s).unwrap())
-                        .collect()
-                })
-                .collect(),
+    fn from_lines(lines: &[SourceLine]) -> Result<Board, ParseError> {
+        if lines.len() < BOARDSIZE {
+            let last = lines.last().map_or(0, |l| l.number);
+            return Err(ParseError::missing(last + 1, "board row"));
         }
+
+        let numbers = lines[0..BOARDSIZE]
+            .iter()
+            .map(|r| {
+                let row = r
+                    .text
+                    .split_whitespace()
+                    .map(|s| r.parse::<i32>(s, "board number"))
+                    .collect::<Result<Vec<i32>, _>>()?;
+                if row.len() != BOARDSIZE {
+                    return Err(r.error(r.text, format!("{} board numbers", BOARDSIZE)));
+                }
+                Ok(row)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Board { numbers })
     }
-    fn from_chunks(chunks: &mut Chunks<&str>) -> Vec<Board> {
-        chunks.map(|c| Board::from_lines(c)).collect()
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Data;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse::<Data>()
+    }
+
+    fn part_1(data: &Self::Input) -> Self::Answer1 {
+        data.score()
+    }
+
+    fn part_2(data: &Self::Input) -> Self::Answer2 {
+        data.losing_score()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
 
     #[test]
     fn day_4_example() {
-        let file = include_str!("../input/day_4_example.txt");
+        let file = &load(2021, 4, Variant::Example(1)).unwrap();
 
         let data = file.parse::<super::Data>().unwrap();
         let result = data.score();
-        assert_eq!(4512, result);
+        assert_answer(2021, 4, Variant::Example(1), 1, result);
 
         let result2 = data.losing_score();
-        assert_eq!(1924, result2);
+        assert_answer(2021, 4, Variant::Example(1), 2, result2);
+    }
+
+    #[test]
+    fn malformed_board() {
+        let file = load(2021, 4, Variant::Example(1))
+            .unwrap()
+            .replacen("22 13", "22 1x", 1);
+
+        assert_eq!(
+            super::ParseError::unexpected(3, 4, "1x", "board number"),
+            file.parse::<super::Data>().err().unwrap()
+        );
     }
 
     #[test]
     fn day_4() {
-        let file = include_str!("../input/day_4.txt");
+        let file = &load(2021, 4, Variant::Real).unwrap();
 
         let data = file.parse::<super::Data>().unwrap();
         let result = data.score();
-        assert_eq!(74320, result);
+        assert_answer(2021, 4, Variant::Real, 1, result);
 
         let result2 = data.losing_score();
-        assert_eq!(17884, result2);
+        assert_answer(2021, 4, Variant::Real, 2, result2);
     }
 }
@@ -1,46 +1,56 @@
+use crate::error::{source_lines, ParseError, SourceLine};
+use crate::solution::Solution;
 use std::cmp::{max, min};
 use std::collections::HashMap;
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
-struct Point {
+pub struct Point {
     pub x: i32,
     pub y: i32,
 }
 
-impl std::str::FromStr for Point {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+impl Point {
+    fn parse(line: &SourceLine, s: &str) -> Result<Self, ParseError> {
         match s.split_once(',') {
             Some((x, y)) => Ok(Point {
-                x: x.trim().parse::<i32>().unwrap(),
-                y: y.trim().parse::<i32>().unwrap(),
+                x: line.parse::<i32>(x, "x coordinate")?,
+                y: line.parse::<i32>(y, "y coordinate")?,
             }),
-            None => Err("Failed to parse point".to_string()),
+            None => Err(line.error(s.trim(), "point 'x,y'")),
         }
     }
 }
 
-struct Line {
+impl std::str::FromStr for Point {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Point::parse(&SourceLine::new(1, s), s)
+    }
+}
+
+pub struct Line {
     pub start: Point,
     pub end: Point,
 }
 
 impl std::str::FromStr for Line {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.split_once("->") {
-            Some((x, y)) => Ok(Line {
-                start: x.parse::<Point>().unwrap(),
-                end: y.parse::<Point>().unwrap(),
-            }),
-            None => Err("Failed to parse line".to_string()),
-        }
+        Line::parse(&SourceLine::new(1, s))
     }
 }
 
 impl Line {
+    fn parse(line: &SourceLine) -> Result<Self, ParseError> {
+        let (start, end) = line.split_once("->")?;
+        Ok(Line {
+            start: Point::parse(line, start)?,
+            end: Point::parse(line, end)?,
+        })
+    }
+
     pub fn is_horizontal(&self) -> bool {
         self.start.y == self.end.y
     }
@@ -66,32 +76,27 @@ impl Line {
     }
 }
 
-struct Lines {
+pub struct Lines {
     pub lines: Vec<Line>,
 }
 
 impl std::str::FromStr for Lines {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Lines {
-            lines: s.lines().map(|s| s.parse::<Line>().unwrap()).collect(),
+            lines: source_lines(s)
+                .map(|l| Line::parse(&l))
+                .collect::<Result<_, _>>()?,
         })
     }
 }
 
+#[derive(Default)]
 struct Grid {
     positions: HashMap<Point, u32>,
 }
 
-impl Default for Grid {
-    fn default() -> Grid {
-        Grid {
-            positions: HashMap::default(),
-        }
-    }
-}
-
 impl Grid {
     pub fn apply_line(&mut self, line: &Line, include_diagonal: bool) {
         if line.is_vertical() {
@@ -139,32 +144,54 @@ fn get_overlaps(lines: &Lines, threshold: u32, include_diagonal: bool) -> u32 {
     grid.num_overlaps(threshold)
 }
 
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Lines;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse::<Lines>()
+    }
+
+    fn part_1(lines: &Self::Input) -> Self::Answer1 {
+        get_overlaps(lines, 2, false)
+    }
+
+    fn part_2(lines: &Self::Input) -> Self::Answer2 {
+        get_overlaps(lines, 2, true)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
 
     #[test]
     fn example() {
-        let file = include_str!("../input/day_5_example.txt");
+        let file = &load(2021, 5, Variant::Example(1)).unwrap();
 
         let lines = file.parse::<super::Lines>().unwrap();
 
         let overlaps = super::get_overlaps(&lines, 2, false);
-        assert_eq!(5, overlaps);
+        assert_answer(2021, 5, Variant::Example(1), 1, overlaps);
 
         let overlaps_diagonal = super::get_overlaps(&lines, 2, true);
-        assert_eq!(12, overlaps_diagonal);
+        assert_answer(2021, 5, Variant::Example(1), 2, overlaps_diagonal);
     }
 
     #[test]
     fn solution() {
-        let file = include_str!("../input/day_5.txt");
+        let file = &load(2021, 5, Variant::Real).unwrap();
 
         let lines = file.parse::<super::Lines>().unwrap();
 
         let overlaps = super::get_overlaps(&lines, 2, false);
-        assert_eq!(4993, overlaps);
+        assert_answer(2021, 5, Variant::Real, 1, overlaps);
 
         let overlaps_diagonal = super::get_overlaps(&lines, 2, true);
-        assert_eq!(21101, overlaps_diagonal);
+        assert_answer(2021, 5, Variant::Real, 2, overlaps_diagonal);
     }
 }
@@ -1,3 +1,5 @@
+use crate::error::{parse_comma_separated, ParseError};
+use crate::solution::Solution;
 use std::collections::HashMap;
 
 fn fish_count(state: &[u8], days: u64) -> u128 {
@@ -23,30 +25,52 @@ fn fish_count(state: &[u8], days: u64) -> u128 {
     daymap.values().fold(0, |acc, x| acc + *x)
 }
 
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<u8>;
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_comma_separated(input, "timer")
+    }
+
+    fn part_1(fish: &Self::Input) -> Self::Answer1 {
+        fish_count(fish, 80)
+    }
+
+    fn part_2(fish: &Self::Input) -> Self::Answer2 {
+        fish_count(fish, 256)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
 
     #[test]
     fn example() {
-        let file = include_str!("../input/day_6_example.txt");
+        let file = &load(2021, 6, Variant::Example(1)).unwrap();
         let data: Vec<u8> = file.split(',').map(|s| s.parse::<u8>().unwrap()).collect();
 
         let after_80_days = super::fish_count(&data, 80);
-        assert_eq!(5934, after_80_days);
+        assert_answer(2021, 6, Variant::Example(1), 1, after_80_days);
 
         let after_256_days = super::fish_count(&data, 256);
-        assert_eq!(26984457539, after_256_days);
+        assert_answer(2021, 6, Variant::Example(1), 2, after_256_days);
     }
 
     #[test]
     fn day_6() {
-        let file = include_str!("../input/day_6.txt");
+        let file = &load(2021, 6, Variant::Real).unwrap();
         let data: Vec<u8> = file.split(',').map(|s| s.parse::<u8>().unwrap()).collect();
 
         let after_80_days = super::fish_count(&data, 80);
-        assert_eq!(373378, after_80_days);
+        assert_answer(2021, 6, Variant::Real, 1, after_80_days);
 
         let after_256_days = super::fish_count(&data, 256);
-        assert_eq!(1682576647495, after_256_days);
+        assert_answer(2021, 6, Variant::Real, 2, after_256_days);
     }
 }
@@ -1,3 +1,5 @@
+use crate::error::{parse_comma_separated, ParseError};
+use crate::solution::Solution;
 fn fuel_cost(target: i32, pos: i32, exponential: bool) -> i32 {
     let dist = i32::abs(pos - target);
     if exponential {
@@ -13,7 +15,6 @@ fn cheapest_position(positions: &[i32], exponential: bool, return_fuel: bool) ->
     );
 
     let costs: Vec<i32> = (smallest..=largest)
-        .into_iter()
         .map(|target| {
             positions
                 .iter()
@@ -31,30 +32,52 @@ fn cheapest_position(positions: &[i32], exponential: bool, return_fuel: bool) ->
     }
 }
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_comma_separated(input, "position")
+    }
+
+    fn part_1(positions: &Self::Input) -> Self::Answer1 {
+        cheapest_position(positions, false, true)
+    }
+
+    fn part_2(positions: &Self::Input) -> Self::Answer2 {
+        cheapest_position(positions, true, true)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
 
     #[test]
     fn example() {
-        let file = include_str!("../input/day_7_example.txt");
+        let file = &load(2021, 7, Variant::Example(1)).unwrap();
         let data: Vec<i32> = file.split(',').map(|s| s.parse::<i32>().unwrap()).collect();
 
         let cheapest = super::cheapest_position(&data, false, false);
         assert_eq!(2, cheapest);
 
         let cheapest_exp = super::cheapest_position(&data, true, true);
-        assert_eq!(168, cheapest_exp);
+        assert_answer(2021, 7, Variant::Example(1), 2, cheapest_exp);
     }
 
     #[test]
     fn actual() {
-        let file = include_str!("../input/day_7.txt");
+        let file = &load(2021, 7, Variant::Real).unwrap();
         let data: Vec<i32> = file.split(',').map(|s| s.parse::<i32>().unwrap()).collect();
 
         let cheapest = super::cheapest_position(&data, false, false);
         assert_eq!(347, cheapest);
 
         let cheapest_exp = super::cheapest_position(&data, true, true);
-        assert_eq!(486, cheapest_exp);
+        assert_answer(2021, 7, Variant::Real, 2, cheapest_exp);
     }
 }
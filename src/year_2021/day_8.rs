@@ -0,0 +1,174 @@
+use crate::error::{source_lines, ParseError, SourceLine};
+use crate::solution::Solution;
+use std::collections::HashSet;
+
+fn is_digit_unique(digit: &str) -> bool {
+    matches!(digit.len(), 2 | 3 | 4 | 7)
+}
+fn part_1_count(line: &str) -> usize {
+    line.split_once('|')
+        .unwrap()
+        .1
+        .split_whitespace()
+        .fold(0, |acc, x| acc + if is_digit_unique(x) { 1 } else { 0 })
+}
+
+fn part_1(file: &str) -> usize {
+    file.lines().map(part_1_count).sum()
+}
+
+fn decode_entry(line: &str) -> u64 {
+    let (signal, output) = line.split_once(" | ").unwrap();
+
+    let splits: Vec<HashSet<u8>> = signal
+        .split_whitespace()
+        .map(|code| code.bytes().collect())
+        .collect();
+
+    let key_1 = splits.iter().find(|&x| x.len() == 2).unwrap();
+    let key_4 = splits.iter().find(|&x| x.len() == 4).unwrap();
+
+    let result = output
+        .split_whitespace()
+        .map(|code| code.bytes().collect())
+        .fold(
+            Vec::with_capacity(4),
+            |mut result: Vec<u64>, digit: HashSet<u8>| {
+                match (
+                    digit.len(),
+                    key_1.intersection(&digit).count(),
+                    key_4.intersection(&digit).count(),
+                ) {
+                    (2, _, _) => result.push(1),
+                    (3, _, _) => result.push(7),
+                    (4, _, _) => result.push(4),
+                    (5, 2, _) => result.push(3),
+                    (5, _, 2) => result.push(2),
+                    (5, _, _) => result.push(5),
+                    (6, 1, _) => result.push(6),
+                    (6, _, 4) => result.push(9),
+                    (6, _, _) => result.push(0),
+                    (7, _, _) => result.push(8),
+                    _ => {}
+                }
+                result
+            },
+        );
+
+    result[0] * 1000 + result[1] * 100 + result[2] * 10 + result[3]
+}
+fn validate_patterns(line: &SourceLine, patterns: &str, count: usize) -> Result<(), ParseError> {
+    let mut found = 0;
+    for pattern in patterns.split_whitespace() {
+        if let Some((i, c)) = pattern
+            .char_indices()
+            .find(|(_, c)| !('a'..='g').contains(c))
+        {
+            return Err(line.error(&pattern[i..i + c.len_utf8()], "segment 'a' to 'g'"));
+        }
+        // a digit lights between 2 and all 7 segments, each of them once
+        let segments = pattern.chars().collect::<HashSet<_>>();
+        if segments.len() != pattern.len() || !(2..=7).contains(&pattern.len()) {
+            return Err(line.error(pattern, "2 to 7 different segments"));
+        }
+        found += 1;
+    }
+
+    if found != count {
+        return Err(line.error(patterns, format!("{} segment patterns", count)));
+    }
+    Ok(())
+}
+
+fn part_2(file: &str) -> u64 {
+    file.lines().map(decode_entry).sum()
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        for line in source_lines(input) {
+            let (signal, output) = line.split_once(" | ")?;
+            validate_patterns(&line, signal, 10)?;
+            validate_patterns(&line, output, 4)?;
+
+            for unique_len in [2, 4] {
+                if !signal.split_whitespace().any(|p| p.len() == unique_len) {
+                    return Err(line.error(signal, format!("a pattern of {} segments", unique_len)));
+                }
+            }
+        }
+
+        Ok(input.to_string())
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
+
+    #[test]
+    fn example() {
+        let file = &load(2021, 8, Variant::Example(1)).unwrap();
+        let count = super::part_1(file);
+        assert_answer(2021, 8, Variant::Example(1), 1, count);
+    }
+
+    #[test]
+    fn example_2() {
+        let file = &load(2021, 8, Variant::Example(2)).unwrap();
+        let count = super::part_2(file);
+        assert_answer(2021, 8, Variant::Example(2), 2, count);
+    }
+
+    #[test]
+    fn actual() {
+        let file = &load(2021, 8, Variant::Real).unwrap();
+        let count = super::part_1(file);
+        assert_answer(2021, 8, Variant::Real, 1, count);
+        let decoded = super::part_2(file);
+        assert_answer(2021, 8, Variant::Real, 2, decoded);
+    }
+
+    #[test]
+    fn malformed() {
+        use super::{Day8, ParseError, Solution};
+
+        assert_eq!(
+            ParseError::unexpected(1, 4, "é", "segment 'a' to 'g'"),
+            Day8::parse("ab é | ab").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::unexpected(1, 1, "ab cd", "' | '"),
+            Day8::parse("ab cd").unwrap_err()
+        );
+
+        let signal = "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb";
+        assert_eq!(
+            ParseError::unexpected(1, 62, "aa", "2 to 7 different segments"),
+            Day8::parse(&format!("{} | aa bb cc dd", signal)).unwrap_err()
+        );
+        assert_eq!(
+            ParseError::unexpected(1, 1, "b", "2 to 7 different segments"),
+            Day8::parse(&format!("b{} | be be be be", &signal[2..])).unwrap_err()
+        );
+        assert_eq!(
+            ParseError::unexpected(1, 62, "be be be", "4 segment patterns"),
+            Day8::parse(&format!("{} | be be be", signal)).unwrap_err()
+        );
+    }
+}
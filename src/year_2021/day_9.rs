@@ -0,0 +1,116 @@
+use crate::common::Grid;
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+#[allow(dead_code)]
+struct Basin {
+    low_point: usize,
+    size: u32,
+}
+
+fn low_point_risk_level(heightmap: &Grid<u32>) -> u32 {
+    let low_points = low_points(heightmap);
+
+    low_points.iter().map(|i| heightmap[*i] + 1).sum()
+}
+
+fn basin_sizes(heightmap: &Grid<u32>) -> u32 {
+    let basins = basins(heightmap);
+    let mut basin_sizes: Vec<u32> = basins.iter().map(|b| b.size).collect();
+    basin_sizes.sort_unstable();
+
+    basin_sizes.iter().rev().take(3).product()
+}
+
+fn low_points(heightmap: &Grid<u32>) -> Vec<usize> {
+    (0..heightmap.len())
+        .filter(|i| is_low_point(heightmap, *i))
+        .collect()
+}
+
+fn is_low_point(heightmap: &Grid<u32>, index: usize) -> bool {
+    let height = heightmap[index];
+    for n in heightmap.neighbors(index, false) {
+        let n_height = heightmap[n];
+        if n_height <= height {
+            return false;
+        }
+    }
+    true
+}
+
+fn basins(heightmap: &Grid<u32>) -> Vec<Basin> {
+    low_points(heightmap)
+        .iter()
+        .map(|i| Basin {
+            low_point: *i,
+            size: basin_size(heightmap, *i),
+        })
+        .collect()
+}
+
+fn basin_size(heightmap: &Grid<u32>, low_point: usize) -> u32 {
+    let mut open_list: Vec<usize> = vec![low_point];
+    let mut closed_list: Vec<usize> = vec![];
+    let mut size = 0;
+    while let Some(point) = open_list.pop() {
+        closed_list.push(point);
+        size += 1;
+
+        for n in heightmap.neighbors(point, false).iter() {
+            if !open_list.contains(n) && !closed_list.contains(n) && heightmap[*n] != 9 {
+                open_list.push(*n);
+            }
+        }
+    }
+    size
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Grid<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse::<Grid<u32>>()
+    }
+
+    fn part_1(heightmap: &Self::Input) -> Self::Answer1 {
+        low_point_risk_level(heightmap)
+    }
+
+    fn part_2(heightmap: &Self::Input) -> Self::Answer2 {
+        basin_sizes(heightmap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::assert_answer;
+    use crate::input::{load, Variant};
+
+    #[test]
+    fn example() {
+        let heightmap = load(2021, 9, Variant::Example(1))
+            .unwrap()
+            .parse::<super::Grid<u32>>()
+            .unwrap();
+        let risk = super::low_point_risk_level(&heightmap);
+        assert_answer(2021, 9, Variant::Example(1), 1, risk);
+        let basins = super::basin_sizes(&heightmap);
+        assert_answer(2021, 9, Variant::Example(1), 2, basins);
+    }
+
+    #[test]
+    fn actual() {
+        let heightmap = load(2021, 9, Variant::Real)
+            .unwrap()
+            .parse::<super::Grid<u32>>()
+            .unwrap();
+        let risk = super::low_point_risk_level(&heightmap);
+        assert_answer(2021, 9, Variant::Real, 1, risk);
+        assert_answer(2021, 9, Variant::Real, 2, super::basin_sizes(&heightmap))
+    }
+}
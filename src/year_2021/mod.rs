@@ -0,0 +1,60 @@
+//! Solutions to the Advent of Code 2021 puzzles.
+
+pub mod day_1;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_2;
+pub mod day_20;
+pub mod day_21;
+pub mod day_22;
+pub mod day_23;
+pub mod day_24;
+pub mod day_25;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+
+use crate::Year;
+
+pub const YEAR: Year = Year {
+    year: 2021,
+    days: &[
+        day!(1, day_1::Day1),
+        day!(2, day_2::Day2),
+        day!(3, day_3::Day3),
+        day!(4, day_4::Day4),
+        day!(5, day_5::Day5),
+        day!(6, day_6::Day6),
+        day!(7, day_7::Day7),
+        day!(8, day_8::Day8),
+        day!(9, day_9::Day9),
+        day!(10, day_10::Day10),
+        day!(11, day_11::Day11),
+        day!(12, day_12::Day12),
+        day!(13, day_13::Day13),
+        day!(14, day_14::Day14),
+        day!(15, day_15::Day15),
+        day!(16, day_16::Day16),
+        day!(17, day_17::Day17),
+        day!(18, day_18::Day18),
+        day!(19, day_19::Day19),
+        day!(20, day_20::Day20),
+        day!(21, day_21::Day21),
+        day!(22, day_22::Day22),
+        day!(23, day_23::Day23),
+        day!(24, day_24::Day24),
+        day!(25, day_25::Day25),
+    ],
+};